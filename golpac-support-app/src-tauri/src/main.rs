@@ -19,7 +19,7 @@ use std::{
     thread::sleep,
     time::Duration,
 };
-use sysinfo::{CpuExt, DiskExt, System, SystemExt};
+use sysinfo::{ComponentExt, CpuExt, DiskExt, PidExt, ProcessExt, System, SystemExt};
 use tauri::{AppHandle, Emitter, Manager, WindowEvent};
 use tauri_plugin_autostart::{MacosLauncher, ManagerExt as AutostartManagerExt};
 
@@ -145,6 +145,8 @@ struct SystemInfo {
     os_version: String,
     ipv4: String,
     domain: Option<String>,
+    #[serde(flatten)]
+    account: AccountDetails,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -208,23 +210,54 @@ struct DiskSnapshot {
     mount: String,
     total_gb: f64,
     free_gb: f64,
+    file_system: String,
+    removable: bool,
+    read_only: Option<bool>,
+    inodes_total: Option<u64>,
+    inodes_used: Option<u64>,
+    inodes_used_percent: Option<f64>,
+}
+
+#[derive(Serialize, Clone, Default)]
+struct DiskIoSnapshot {
+    device: String,
+    read_bytes_per_sec: f64,
+    write_bytes_per_sec: f64,
+    reads_per_sec: f64,
+    writes_per_sec: f64,
+    // Average time a request spent queued + serviced during the sample window.
+    await_ms: Option<f64>,
+    busy_percent: f64,
 }
 
 #[derive(Serialize, Default)]
 struct SystemMetrics {
     uptime_seconds: u64,
     uptime_human: String,
+    // Historical names: these describe the system volume (C: on Windows, / elsewhere).
     free_disk_c_gb: f64,
     total_disk_c_gb: f64,
+    system_mount: Option<String>,
     cpu_usage_percent: f32,
+    cpu_temperature_c: Option<f32>,
+    cpu_frequency_mhz: Option<f64>,
+    cpu_max_frequency_mhz: Option<f64>,
+    cpu_throttling: bool,
+    throttle_episodes: Vec<ThrottleEpisode>,
     memory_used_gb: f64,
     memory_total_gb: f64,
+    swap_used_gb: f64,
+    swap_total_gb: f64,
     default_gateway: Option<String>,
     gateway_ping_ms: Option<f64>,
     public_ip: Option<String>,
     timestamp: String,
     disks: Vec<DiskSnapshot>,
+    disk_io: Vec<DiskIoSnapshot>,
+    io_wait_percent: Option<f64>,
+    battery: Option<BatteryStatus>,
     cpu_brand: Option<String>,
+    encryption: EncryptionStatus,
     #[cfg(target_os = "windows")]
     bitlocker: Vec<BitlockerVolume>,
 }
@@ -255,13 +288,19 @@ struct DriverStatus {
     outdated_count: usize,
     sample: Vec<DriverEntry>,
     raw: Option<String>,
+    // Linux: /proc/sys/kernel/tainted bitmask and every finding behind the count.
+    kernel_taint: Option<u64>,
+    issues: Vec<DriverIssue>,
 }
 
 #[derive(Serialize, Deserialize)]
 struct AvProduct {
     name: String,
     running: bool,
+    #[serde(alias = "lastScan")]
     last_scan: Option<String>,
+    #[serde(default)]
+    definitions_age_days: Option<f64>,
 }
 
 #[derive(Serialize, Clone, Default)]
@@ -284,8 +323,15 @@ struct PingSummary {
     raw_output: String,
 }
 
+// Account and directory lookups spawn processes, so keep them off the main thread.
 #[tauri::command]
-fn get_system_info() -> SystemInfo {
+async fn get_system_info() -> Result<SystemInfo, String> {
+    tauri::async_runtime::spawn_blocking(collect_system_info)
+        .await
+        .map_err(|e| format!("Thread join error: {e}"))
+}
+
+fn collect_system_info() -> SystemInfo {
     use local_ip_address::local_ip;
 
     let hostname = whoami::fallible::hostname().unwrap_or_else(|_| "Unknown".to_string());
@@ -296,8 +342,11 @@ fn get_system_info() -> SystemInfo {
         .unwrap_or_else(|_| "Unknown".to_string());
     #[cfg(target_os = "windows")]
     let domain = std::env::var("USERDOMAIN").ok();
-    #[cfg(not(target_os = "windows"))]
+    #[cfg(target_os = "linux")]
+    let domain = linux_directory_domain();
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
     let domain = None;
+    let account = account_details(&username);
 
     SystemInfo {
         hostname,
@@ -305,6 +354,7 @@ fn get_system_info() -> SystemInfo {
         os_version,
         ipv4,
         domain,
+        account,
     }
 }
 
@@ -389,34 +439,29 @@ fn get_bitlocker_status() -> Vec<BitlockerVolume> {
     Vec::new()
 }
 
-#[cfg(target_os = "windows")]
-fn launch_antivirus_impl(product: String) -> Result<(), String> {
-    let name = product.to_lowercase();
-    let mut candidates: Vec<&str> = Vec::new();
-    if name.contains("webroot") {
-        candidates.push(r"C:\Program Files\Webroot\WRSA.exe");
-        candidates.push(r"C:\Program Files (x86)\Webroot\WRSA.exe");
-    } else if name.contains("malwarebytes") {
-        candidates.push(r"C:\Program Files\Malwarebytes\Anti-Malware\mbam.exe");
-        candidates.push(r"C:\Program Files\Malwarebytes\Anti-Malware\MBAMService.exe");
-    } else if name.contains("checkpoint") || name.contains("check point") {
-        candidates
-            .push(r"C:\Program Files (x86)\CheckPoint\Endpoint Security\Endpoint Connect\trac.exe");
+fn launch_antivirus_impl(app: &AppHandle, product: String) -> Result<(), String> {
+    let catalog = load_av_catalog(app);
+    let candidates: Vec<AvLaunchCommand> = catalog
+        .find(&product, current_platform())
+        .map(|entry| entry.launch.clone())
+        .unwrap_or_default()
+        .into_iter()
+        // last resort try raw
+        .chain(cfg!(target_os = "windows").then(|| AvLaunchCommand {
+            program: product.clone(),
+            args: Vec::new(),
+        }))
+        .collect();
+    if candidates.is_empty() {
+        return Err(format!("No launch command configured for {product}"));
     }
-    // last resort try raw
-    candidates.push(&product);
 
-    for path in candidates {
-        let cmd_path = Path::new(path);
-        let target = if cmd_path.exists() {
-            cmd_path.to_path_buf()
-        } else {
-            PathBuf::from(path)
-        };
-        let result = Command::new(target)
-            .creation_flags(CREATE_NO_WINDOW)
-            .spawn();
-        if result.is_ok() {
+    for candidate in candidates {
+        let mut cmd = Command::new(&candidate.program);
+        cmd.args(&candidate.args);
+        #[cfg(target_os = "windows")]
+        cmd.creation_flags(CREATE_NO_WINDOW);
+        if cmd.spawn().is_ok() {
             return Ok(());
         }
     }
@@ -424,11 +469,6 @@ fn launch_antivirus_impl(product: String) -> Result<(), String> {
     Err("Could not launch antivirus process".to_string())
 }
 
-#[cfg(not(target_os = "windows"))]
-fn launch_antivirus_impl(_product: String) -> Result<(), String> {
-    Err("Launching antivirus is only supported on Windows.".to_string())
-}
-
 #[tauri::command]
 fn get_driver_status() -> Result<DriverStatus, String> {
     #[cfg(target_os = "windows")]
@@ -525,12 +565,18 @@ fn get_driver_status() -> Result<DriverStatus, String> {
             outdated_count: count,
             sample: entries,
             raw: Some(stdout),
+            ..Default::default()
         })
     }
 
-    #[cfg(not(target_os = "windows"))]
+    #[cfg(target_os = "linux")]
+    {
+        Ok(get_driver_status_linux())
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
     {
-        Err("Driver check is only supported on Windows.".to_string())
+        Err("Driver check is only supported on Windows and Linux.".to_string())
     }
 }
 
@@ -1595,29 +1641,50 @@ fn get_system_metrics() -> Result<SystemMetrics, String> {
     system.refresh_memory();
     system.refresh_disks();
     system.refresh_cpu();
+    // Disk counters are sampled across the same window the CPU refresh needs.
+    let io_before = sample_disk_io();
     sleep(Duration::from_millis(250));
     system.refresh_cpu();
+    let io_after = sample_disk_io();
+    let (disk_io, io_wait_percent) = diff_disk_io(&io_before, &io_after);
 
     let uptime = system.uptime();
     let uptime_human = format_duration(uptime);
 
+    let system_mount = system_volume_mount();
+    let mount_flags = read_mount_flags();
+    let inode_usage = read_inode_usage();
+
     let mut free_disk_c = 0f64;
     let mut total_disk_c = 0f64;
+    let mut found_system_mount = false;
     let mut disks: Vec<DiskSnapshot> = Vec::new();
     for disk in system.disks() {
         let mount = disk.mount_point().to_string_lossy().to_string();
-        if mount.to_uppercase().starts_with("C:") {
+        if !found_system_mount && same_mount(&mount, &system_mount) {
+            found_system_mount = true;
             free_disk_c = bytes_to_gb(disk.available_space());
             total_disk_c = bytes_to_gb(disk.total_space());
         }
         let name = disk.name().to_string_lossy().trim().to_string();
         let label = if name.is_empty() { mount.clone() } else { name };
+        let inodes = inode_usage.get(&mount);
 
         disks.push(DiskSnapshot {
             name: label,
             mount: mount.clone(),
             total_gb: bytes_to_gb(disk.total_space()),
             free_gb: bytes_to_gb(disk.available_space()),
+            file_system: String::from_utf8_lossy(disk.file_system())
+                .trim()
+                .to_string(),
+            removable: disk.is_removable(),
+            read_only: mount_flags.get(&mount).copied(),
+            inodes_total: inodes.map(|i| i.0),
+            inodes_used: inodes.map(|i| i.1),
+            inodes_used_percent: inodes
+                .filter(|i| i.0 > 0)
+                .map(|i| (i.1 as f64 / i.0 as f64 * 1000.0).round() / 10.0),
         });
     }
 
@@ -1629,10 +1696,14 @@ fn get_system_metrics() -> Result<SystemMetrics, String> {
         Some(cpu_brand)
     };
 
-    let total_mem = system.total_memory();
-    let used_mem = system.used_memory();
-    let memory_total_gb = kib_to_gb(total_mem);
-    let memory_used_gb = kib_to_gb(used_mem);
+    let mut temperatures = read_component_temperatures(&system);
+    temperatures.extend(read_thermal_zones());
+    let (cpu_frequency_mhz, cpu_max_frequency_mhz, _) = read_cpu_frequency();
+    let (cpu_throttling, throttle_episodes) = thermal_episodes();
+
+    // sysinfo reports memory in bytes.
+    let memory_total_gb = bytes_to_gb(system.total_memory());
+    let memory_used_gb = bytes_to_gb(system.used_memory());
 
     let gateway = default_gateway();
     let ping = gateway.as_ref().and_then(|g| ping_gateway(g));
@@ -1644,84 +1715,185 @@ fn get_system_metrics() -> Result<SystemMetrics, String> {
 
     #[cfg(target_os = "windows")]
     let bitlocker = get_bitlocker_status();
+    #[cfg(target_os = "windows")]
+    let encryption = encryption_from_bitlocker(&bitlocker);
+    #[cfg(not(target_os = "windows"))]
+    let encryption = get_encryption_status_impl();
 
-    Ok(SystemMetrics {
+    let metrics = SystemMetrics {
         uptime_seconds: uptime,
         uptime_human,
         free_disk_c_gb: free_disk_c,
         total_disk_c_gb: total_disk_c,
+        system_mount: found_system_mount.then_some(system_mount),
         cpu_usage_percent: cpu_usage,
+        cpu_temperature_c: pick_cpu_temperature(&temperatures),
+        cpu_frequency_mhz,
+        cpu_max_frequency_mhz,
+        cpu_throttling,
+        throttle_episodes,
         memory_used_gb,
         memory_total_gb,
+        swap_used_gb: bytes_to_gb(system.used_swap()),
+        swap_total_gb: bytes_to_gb(system.total_swap()),
         default_gateway: gateway,
         gateway_ping_ms: ping,
         public_ip,
         timestamp: Utc::now().to_rfc3339(),
         disks,
+        disk_io,
+        io_wait_percent,
+        battery: read_battery_status(),
         cpu_brand,
+        encryption,
         #[cfg(target_os = "windows")]
         bitlocker,
-    })
+    };
+    record_metrics_sample(&metrics);
+    Ok(metrics)
 }
 
-#[tauri::command]
-fn get_app_context(category: String) -> Result<AppContextInfo, String> {
-    let normalized = category.trim().to_lowercase();
-    let details = match normalized.as_str() {
-        #[cfg(target_os = "windows")]
-        "sage 300" => gather_sage_context().ok(),
-        #[cfg(target_os = "windows")]
-        "adobe" => gather_adobe_context().ok(),
-        #[cfg(target_os = "windows")]
-        "office 365" | "email" => gather_office_context().ok(),
-        _ => None,
-    };
+//
+// ───────── Metrics history ─────────
+//
+
+const METRICS_HISTORY_MAX: usize = 200;
+
+#[derive(Serialize, Clone)]
+struct BatterySample {
+    name: String,
+    charge_percent: Option<f64>,
+    wear_percent: Option<f64>,
+    health: String,
+}
+
+/// The trend-worthy part of one `get_system_metrics` call.
+#[derive(Serialize, Clone)]
+struct MetricsSample {
+    timestamp: String,
+    cpu_usage_percent: f32,
+    memory_used_gb: f64,
+    free_disk_c_gb: f64,
+    ac_online: Option<bool>,
+    batteries: Vec<BatterySample>,
+}
 
-    Ok(AppContextInfo { category, details })
+static METRICS_HISTORY: once_cell::sync::Lazy<
+    std::sync::Mutex<std::collections::VecDeque<MetricsSample>>,
+> = once_cell::sync::Lazy::new(|| std::sync::Mutex::new(std::collections::VecDeque::new()));
+
+fn record_metrics_sample(metrics: &SystemMetrics) {
+    let battery = metrics.battery.as_ref();
+    let sample = MetricsSample {
+        timestamp: metrics.timestamp.clone(),
+        cpu_usage_percent: metrics.cpu_usage_percent,
+        memory_used_gb: metrics.memory_used_gb,
+        free_disk_c_gb: metrics.free_disk_c_gb,
+        ac_online: battery.and_then(|b| b.ac_online),
+        batteries: battery
+            .map(|b| {
+                b.batteries
+                    .iter()
+                    .map(|info| BatterySample {
+                        name: info.name.clone(),
+                        charge_percent: info.charge_percent,
+                        wear_percent: info.wear_percent,
+                        health: info.health.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+    if let Ok(mut history) = METRICS_HISTORY.lock() {
+        history.push_back(sample);
+        while history.len() > METRICS_HISTORY_MAX {
+            history.pop_front();
+        }
+    }
 }
 
+/// Samples from earlier `get_system_metrics` calls this session, oldest first.
 #[tauri::command]
-fn get_antivirus_status() -> Result<Vec<AvProduct>, String> {
-    #[cfg(target_os = "windows")]
-    {
-        let script = r#"
-$products = @(
-  @{ Name = 'Webroot'; Processes = @('WRSA'); Services = @('WRSVC'); RegKey = 'HKLM:\SOFTWARE\WOW6432Node\Webroot\AV'; RegValue = 'LastScan' },
-  @{ Name = 'Checkpoint'; Processes = @('cpd','epwd'); Services = @('epwd'); RegKey = $null; RegValue = $null },
-  @{ Name = 'Malwarebytes'; Processes = @('MBAMService','mbam'); Services = @('MBAMService'); RegKey = 'HKLM:\SOFTWARE\Malwarebytes\MWAC'; RegValue = 'LastAssetScan' }
-)
+fn get_metrics_history() -> Result<Vec<MetricsSample>, String> {
+    METRICS_HISTORY
+        .lock()
+        .map(|history| history.iter().cloned().collect())
+        .map_err(|_| "Metrics history unavailable".to_string())
+}
+
+#[cfg(target_os = "windows")]
+const AV_STATUS_SCRIPT: &str = r#"
+$products = '__CATALOG__' | ConvertFrom-Json
 
 $results = @()
 foreach ($p in $products) {
   $running = $false
 
-  foreach ($proc in $p.Processes) {
-    if (Get-Process -Name $proc -ErrorAction SilentlyContinue) { $running = $true; break }
+  foreach ($proc in @($p.processes)) {
+    if ($proc -and (Get-Process -Name $proc -ErrorAction SilentlyContinue)) { $running = $true; break }
   }
-  if (-not $running -and $p.Services) {
-    foreach ($svc in $p.Services) {
+  if (-not $running) {
+    foreach ($svc in @($p.services)) {
+      if (-not $svc) { continue }
       $service = Get-Service -Name $svc -ErrorAction SilentlyContinue
       if ($service -and $service.Status -eq 'Running') { $running = $true; break }
     }
   }
 
+  # Entries listing install paths are only reported when actually installed.
+  if (-not $running -and @($p.install_paths).Count -gt 0) {
+    $installed = $false
+    foreach ($path in @($p.install_paths)) {
+      if ($path -and (Test-Path $path)) { $installed = $true; break }
+    }
+    if (-not $installed) { continue }
+  }
+
   $lastScan = $null
-  if ($p.RegKey -and (Test-Path $p.RegKey)) {
-    $val = (Get-ItemProperty -Path $p.RegKey -ErrorAction SilentlyContinue).$($p.RegValue)
-    if ($val) { $lastScan = $val }
+  $ls = $p.last_scan
+  if ($ls -and $ls.registry_key -and (Test-Path $ls.registry_key)) {
+    $val = (Get-ItemProperty -Path $ls.registry_key -ErrorAction SilentlyContinue).$($ls.registry_value)
+    if ($val) { $lastScan = [string]$val }
+  } elseif ($ls -and $ls.path -and (Test-Path $ls.path)) {
+    $lastScan = (Get-Item $ls.path).LastWriteTime.ToString('o')
+  }
+
+  $defAge = $null
+  $defs = $p.definitions
+  if ($defs -and $defs.path -and (Test-Path $defs.path)) {
+    $newest = Get-ChildItem -Path $defs.path -File -ErrorAction SilentlyContinue |
+      Sort-Object LastWriteTime -Descending | Select-Object -First 1
+    if (-not $newest) { $newest = Get-Item $defs.path }
+    $defAge = [math]::Round(((Get-Date) - $newest.LastWriteTime).TotalDays, 1)
   }
 
   $results += [PSCustomObject]@{
-    name = $p.Name
+    name = $p.name
     running = $running
     lastScan = $lastScan
+    definitions_age_days = $defAge
   }
 }
 
-$results | ConvertTo-Json -Compress
+ConvertTo-Json -InputObject @($results) -Compress
 "#;
 
-        let output = powershell_output(script)?;
+#[tauri::command]
+fn get_antivirus_status(app: AppHandle) -> Result<Vec<AvProduct>, String> {
+    let catalog = load_av_catalog(&app);
+
+    #[cfg(target_os = "windows")]
+    {
+        let entries: Vec<&AvCatalogEntry> = catalog.for_platform("windows").collect();
+        if entries.is_empty() {
+            return Ok(Vec::new());
+        }
+        let json = serde_json::to_string(&entries)
+            .map_err(|e| format!("Serialize AV catalog failed: {e}"))?;
+        // Embedded as a single-quoted PowerShell string.
+        let script = AV_STATUS_SCRIPT.replace("__CATALOG__", &json.replace('\'', "''"));
+
+        let output = powershell_output(&script)?;
         if output.trim().is_empty() {
             return Ok(Vec::new());
         }
@@ -1731,8 +1903,14 @@ $results | ConvertTo-Json -Compress
         Ok(parsed)
     }
 
-    #[cfg(not(target_os = "windows"))]
+    #[cfg(target_os = "linux")]
+    {
+        Ok(detect_catalog_products_linux(&catalog))
+    }
+
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
     {
+        let _ = catalog;
         Ok(Vec::new())
     }
 }
@@ -1839,8 +2017,8 @@ fn launch_quick_assist() -> Result<(), String> {
 }
 
 #[tauri::command]
-fn launch_antivirus(product: String) -> Result<(), String> {
-    launch_antivirus_impl(product)
+fn launch_antivirus(app: AppHandle, product: String) -> Result<(), String> {
+    launch_antivirus_impl(&app, product)
 }
 
 #[cfg(target_os = "windows")]
@@ -1919,8 +2097,221 @@ fn bytes_to_gb(bytes: u64) -> f64 {
     bytes as f64 / 1024.0 / 1024.0 / 1024.0
 }
 
-fn kib_to_gb(kib: u64) -> f64 {
-    kib as f64 / 1024.0 / 1024.0
+
+//
+// ───────── Disk volumes & I/O ─────────
+//
+
+#[cfg(target_os = "windows")]
+fn system_volume_mount() -> String {
+    let drive = env::var("SystemDrive").unwrap_or_else(|_| "C:".to_string());
+    format!("{}\\", drive.trim_end_matches('\\'))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn system_volume_mount() -> String {
+    "/".to_string()
+}
+
+fn same_mount(mount: &str, system_mount: &str) -> bool {
+    let a = mount.trim_end_matches(['\\', '/']);
+    let b = system_mount.trim_end_matches(['\\', '/']);
+    a.eq_ignore_ascii_case(b)
+}
+
+/// `/proc/mounts` escapes whitespace in paths as octal (`\040`).
+#[cfg(target_os = "linux")]
+fn unescape_mount_path(raw: &str) -> String {
+    raw.replace("\\040", " ")
+        .replace("\\011", "\t")
+        .replace("\\012", "\n")
+        .replace("\\134", "\\")
+}
+
+/// Mount point → read-only flag.
+#[cfg(target_os = "linux")]
+fn read_mount_flags() -> std::collections::HashMap<String, bool> {
+    let mut flags = std::collections::HashMap::new();
+    let text = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+    for line in text.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 4 {
+            continue;
+        }
+        let read_only = fields[3].split(',').any(|opt| opt == "ro");
+        flags.insert(unescape_mount_path(fields[1]), read_only);
+    }
+    flags
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_mount_flags() -> std::collections::HashMap<String, bool> {
+    std::collections::HashMap::new()
+}
+
+/// Mount point → (total inodes, used inodes). Local filesystems only so a hung
+/// network share can't stall the metrics call.
+#[cfg(target_os = "linux")]
+fn read_inode_usage() -> std::collections::HashMap<String, (u64, u64)> {
+    let mut usage = std::collections::HashMap::new();
+    let Ok(output) = Command::new("df").args(["-P", "-i", "-l"]).output() else {
+        return usage;
+    };
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    for line in stdout.lines().skip(1) {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 6 {
+            continue;
+        }
+        let (Ok(total), Ok(used)) = (fields[1].parse::<u64>(), fields[2].parse::<u64>()) else {
+            continue;
+        };
+        // Filesystems without fixed inode tables (btrfs, vfat) report zero.
+        if total == 0 {
+            continue;
+        }
+        usage.insert(fields[5..].join(" "), (total, used));
+    }
+    usage
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_inode_usage() -> std::collections::HashMap<String, (u64, u64)> {
+    std::collections::HashMap::new()
+}
+
+#[derive(Clone, Copy, Default)]
+struct DiskStatCounters {
+    reads: u64,
+    sectors_read: u64,
+    read_ms: u64,
+    writes: u64,
+    sectors_written: u64,
+    write_ms: u64,
+    io_ms: u64,
+}
+
+struct DiskIoSample {
+    taken: std::time::Instant,
+    devices: std::collections::HashMap<String, DiskStatCounters>,
+    // (total jiffies, iowait jiffies) from the aggregate `cpu` line of /proc/stat.
+    cpu_jiffies: Option<(u64, u64)>,
+}
+
+#[cfg(target_os = "linux")]
+fn sample_disk_io() -> DiskIoSample {
+    let taken = std::time::Instant::now();
+    let mut devices = std::collections::HashMap::new();
+
+    let diskstats = std::fs::read_to_string("/proc/diskstats").unwrap_or_default();
+    for line in diskstats.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 14 {
+            continue;
+        }
+        let name = fields[2];
+        // Only whole block devices; partitions would double-count and
+        // loop/ram devices are noise.
+        if name.starts_with("loop") || name.starts_with("ram") || name.starts_with("zram") {
+            continue;
+        }
+        if !std::path::Path::new("/sys/block").join(name).exists() {
+            continue;
+        }
+        let num = |idx: usize| fields[idx].parse::<u64>().unwrap_or(0);
+        devices.insert(
+            name.to_string(),
+            DiskStatCounters {
+                reads: num(3),
+                sectors_read: num(5),
+                read_ms: num(6),
+                writes: num(7),
+                sectors_written: num(9),
+                write_ms: num(10),
+                io_ms: num(12),
+            },
+        );
+    }
+
+    let cpu_jiffies = std::fs::read_to_string("/proc/stat").ok().and_then(|text| {
+        let line = text.lines().find(|l| l.starts_with("cpu "))?.to_string();
+        let values: Vec<u64> = line
+            .split_whitespace()
+            .skip(1)
+            .take(8)
+            .filter_map(|v| v.parse().ok())
+            .collect();
+        if values.len() < 5 {
+            return None;
+        }
+        Some((values.iter().sum(), values[4]))
+    });
+
+    DiskIoSample {
+        taken,
+        devices,
+        cpu_jiffies,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn sample_disk_io() -> DiskIoSample {
+    DiskIoSample {
+        taken: std::time::Instant::now(),
+        devices: std::collections::HashMap::new(),
+        cpu_jiffies: None,
+    }
+}
+
+fn diff_disk_io(before: &DiskIoSample, after: &DiskIoSample) -> (Vec<DiskIoSnapshot>, Option<f64>) {
+    // /proc/diskstats always counts 512-byte sectors regardless of the device.
+    const SECTOR_BYTES: f64 = 512.0;
+    let secs = after.taken.duration_since(before.taken).as_secs_f64();
+    if secs <= 0.0 {
+        return (Vec::new(), None);
+    }
+
+    let mut devices: Vec<DiskIoSnapshot> = after
+        .devices
+        .iter()
+        .filter_map(|(name, now)| {
+            let prev = before.devices.get(name)?;
+            // Skip devices that have never seen I/O (empty card readers, etc.).
+            if now.reads == 0 && now.writes == 0 {
+                return None;
+            }
+            let reads = now.reads.saturating_sub(prev.reads);
+            let writes = now.writes.saturating_sub(prev.writes);
+            let wait_ms = now.read_ms.saturating_sub(prev.read_ms)
+                + now.write_ms.saturating_sub(prev.write_ms);
+            let busy_ms = now.io_ms.saturating_sub(prev.io_ms) as f64;
+            Some(DiskIoSnapshot {
+                device: name.clone(),
+                read_bytes_per_sec: now.sectors_read.saturating_sub(prev.sectors_read) as f64
+                    * SECTOR_BYTES
+                    / secs,
+                write_bytes_per_sec: now.sectors_written.saturating_sub(prev.sectors_written)
+                    as f64
+                    * SECTOR_BYTES
+                    / secs,
+                reads_per_sec: reads as f64 / secs,
+                writes_per_sec: writes as f64 / secs,
+                await_ms: (reads + writes > 0).then(|| wait_ms as f64 / (reads + writes) as f64),
+                busy_percent: (busy_ms / (secs * 1000.0) * 100.0).min(100.0),
+            })
+        })
+        .collect();
+    devices.sort_by(|a, b| a.device.cmp(&b.device));
+
+    let io_wait_percent = match (before.cpu_jiffies, after.cpu_jiffies) {
+        (Some((total_a, wait_a)), Some((total_b, wait_b))) if total_b > total_a => {
+            let pct = wait_b.saturating_sub(wait_a) as f64 / (total_b - total_a) as f64 * 100.0;
+            Some((pct * 10.0).round() / 10.0)
+        }
+        _ => None,
+    };
+
+    (devices, io_wait_percent)
 }
 
 #[cfg(target_os = "windows")]
@@ -1997,95 +2388,6 @@ fn fetch_public_ip() -> Option<String> {
         .filter(|s| !s.trim().is_empty())
 }
 
-#[cfg(target_os = "windows")]
-fn gather_sage_context() -> Result<String, String> {
-    let script = r#"
-$paths = @(
-  'HKLM:\SOFTWARE\ACCPAC International, Inc.\ACCPAC\Configuration',
-  'HKLM:\SOFTWARE\WOW6432Node\ACCPAC International, Inc.\ACCPAC\Configuration'
-)
-foreach ($p in $paths) {
-  if (Test-Path $p) {
-    $item = Get-ItemProperty -Path $p -ErrorAction SilentlyContinue
-    if ($item) {
-      [PSCustomObject]@{
-        Version = $item.Version
-        SharedData = $item.SharedData
-      } | ConvertTo-Json -Compress
-      break
-    }
-  }
-}
-"#;
-    powershell_output(script)
-}
-
-#[cfg(target_os = "windows")]
-fn gather_adobe_context() -> Result<String, String> {
-    let script = r#"
-$roots = @(
-  'HKLM:\SOFTWARE\Adobe',
-  'HKLM:\SOFTWARE\WOW6432Node\Adobe'
-)
-foreach ($root in $roots) {
-  if (Test-Path $root) {
-    $sub = Get-ChildItem -Path $root -Recurse -ErrorAction SilentlyContinue |
-      Where-Object { $_.PSChildName -match 'Acrobat' -or $_.PSChildName -match 'Reader' } |
-      Select-Object -First 1
-    if ($sub) {
-      $item = Get-ItemProperty $sub.PSPath -ErrorAction SilentlyContinue
-      if ($item) {
-        $product = $item.ProductName
-        if (-not $product -and $item.DisplayName) { $product = $item.DisplayName }
-        if (-not $product) { $product = $sub.PSChildName }
-
-        $version = $item.Version
-        if (-not $version -and $item.DisplayVersion) { $version = $item.DisplayVersion }
-
-        $install = $item.InstallPath
-        if (-not $install -and $item.Path) { $install = $item.Path }
-        if (-not $install -and $item.InstallDir) { $install = $item.InstallDir }
-
-        [PSCustomObject]@{
-          Product = $product
-          Version = $version
-          InstallLocation = $install
-        } | ConvertTo-Json -Compress
-        break
-      }
-    }
-  }
-}
-"#;
-    powershell_output(script)
-}
-
-#[cfg(target_os = "windows")]
-fn gather_office_context() -> Result<String, String> {
-    let script = r#"
-$configPath = 'HKLM:\SOFTWARE\Microsoft\Office\ClickToRun\Configuration'
-$profilePath = 'HKCU:\Software\Microsoft\Office\16.0\Outlook'
-$obj = [ordered]@{}
-if (Test-Path $configPath) {
-  $item = Get-ItemProperty $configPath -ErrorAction SilentlyContinue
-  if ($item) {
-    $obj.Version = $item.VersionToReport
-    $obj.Product = $item.ProductReleaseIds
-  }
-}
-if (Test-Path $profilePath) {
-  $p = Get-ItemProperty $profilePath -ErrorAction SilentlyContinue
-  if ($p) {
-    $obj.DefaultProfile = $p.DefaultProfile
-  }
-}
-if ($obj.Keys.Count -gt 0) {
-  $obj | ConvertTo-Json -Compress
-}
-"#;
-    powershell_output(script)
-}
-
 #[cfg(target_os = "windows")]
 fn powershell_output(script: &str) -> Result<String, String> {
     let output = Command::new("powershell")
@@ -2717,73 +3019,9259 @@ fn get_usage_snapshot() -> Result<UsageSnapshot, String> {
 }
 
 //
-// ───────── Tauri main ─────────
+// ───────── Battery ─────────
 //
 
-fn main() {
-    let builder = tauri::Builder::default()
-        .plugin(tauri_plugin_single_instance::init(|_app, _args, _cwd| {
-            #[cfg(target_os = "windows")]
-            {
-                reveal_main_window(&_app);
-            }
-        }))
-        .plugin(tauri_plugin_autostart::init(
-            MacosLauncher::LaunchAgent,
-            None,
-        ));
+#[cfg(target_os = "linux")]
+const BATTERY_WEAR_WARN_PERCENT: f64 = 20.0;
+#[cfg(target_os = "linux")]
+const BATTERY_WEAR_REPLACE_PERCENT: f64 = 40.0;
+#[cfg(target_os = "linux")]
+const BATTERY_CYCLE_WARN: u64 = 800;
+#[cfg(target_os = "linux")]
+const BATTERY_LOW_PERCENT: f64 = 10.0;
 
-    #[cfg(target_os = "windows")]
-    let builder = builder.plugin(tauri_plugin_notification::init());
+#[derive(Serialize, Clone, Default)]
+struct BatteryInfo {
+    name: String,
+    manufacturer: Option<String>,
+    model: Option<String>,
+    technology: Option<String>,
+    charge_percent: Option<f64>,
+    state: String,
+    design_capacity_wh: Option<f64>,
+    full_capacity_wh: Option<f64>,
+    wear_percent: Option<f64>,
+    cycle_count: Option<u64>,
+    time_remaining_minutes: Option<u64>,
+    health: String,
+    warnings: Vec<String>,
+}
 
-    builder
-        .invoke_handler(tauri::generate_handler![
-            get_system_info,
-            get_printers,
-            capture_screenshot,
-            launch_quick_assist,
-            get_system_metrics,
-            get_app_context,
-            get_vpn_status,
-            test_internet_connection,
-            get_antivirus_status,
-            launch_antivirus,
-            get_driver_status,
-            exit_application,
-            read_ticket_history,
-            write_ticket_history,
-            get_usage_snapshot
-        ])
-        .setup(|app| {
-            if let Err(e) = app.autolaunch().enable() {
-                eprintln!("Failed to enable autostart: {e}");
-            }
-            #[cfg(not(target_os = "windows"))]
-            let _ = app;
+#[derive(Serialize, Clone, Default)]
+struct BatteryStatus {
+    ac_online: Option<bool>,
+    batteries: Vec<BatteryInfo>,
+    timestamp: String,
+}
 
-            #[cfg(target_os = "windows")]
-            {
-                setup_windows_tray(app)?;
-                ensure_notification_permission(&app.handle());
-                start_target_still_monitor(&app.handle());
-                start_video_recorder(&app.handle());
-                start_video_uploader(&app.handle());
-            }
-            monitor_network(app.handle().clone());
+#[cfg(target_os = "linux")]
+fn read_sysfs_string(dir: &std::path::Path, file: &str) -> Option<String> {
+    std::fs::read_to_string(dir.join(file))
+        .ok()
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
 
-            Ok(())
-        })
-        // Close button → hide window (app keeps running)
-        .on_window_event(|window, event| {
-            if let WindowEvent::CloseRequested { api, .. } = event {
-                api.prevent_close();
-                let _ = window.hide();
-                #[cfg(target_os = "windows")]
-                {
-                    show_background_notification(&window.app_handle());
+#[cfg(target_os = "linux")]
+fn read_sysfs_u64(dir: &std::path::Path, file: &str) -> Option<u64> {
+    read_sysfs_string(dir, file).and_then(|s| s.parse().ok())
+}
+
+#[cfg(target_os = "linux")]
+fn read_battery_status() -> Option<BatteryStatus> {
+    let entries = std::fs::read_dir("/sys/class/power_supply").ok()?;
+
+    let mut ac_online: Option<bool> = None;
+    let mut batteries: Vec<BatteryInfo> = Vec::new();
+
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        match read_sysfs_string(&dir, "type").as_deref() {
+            Some("Mains") | Some("USB") => {
+                if let Some(online) = read_sysfs_u64(&dir, "online") {
+                    ac_online = Some(ac_online.unwrap_or(false) || online == 1);
                 }
             }
-        })
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+            Some("Battery") => {
+                // Peripheral batteries (mice, headsets) report scope=Device.
+                if read_sysfs_string(&dir, "scope").as_deref() == Some("Device") {
+                    continue;
+                }
+                if read_sysfs_u64(&dir, "present") == Some(0) {
+                    continue;
+                }
+                batteries.push(read_linux_battery(&dir, name));
+            }
+            _ => {}
+        }
+    }
+
+    if batteries.is_empty() && ac_online.is_none() {
+        return None;
+    }
+    batteries.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Some(BatteryStatus {
+        ac_online,
+        batteries,
+        timestamp: Utc::now().to_rfc3339(),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn read_linux_battery(dir: &std::path::Path, name: String) -> BatteryInfo {
+    // Drivers expose either energy_* (µWh, power in µW) or charge_* (µAh,
+    // current in µA). Charge values are converted to Wh with the design voltage.
+    let voltage_v = read_sysfs_u64(dir, "voltage_min_design")
+        .or_else(|| read_sysfs_u64(dir, "voltage_now"))
+        .map(|uv| uv as f64 / 1_000_000.0);
+    let to_wh = |energy_file: &str, charge_file: &str| -> Option<f64> {
+        if let Some(uwh) = read_sysfs_u64(dir, energy_file) {
+            return Some(uwh as f64 / 1_000_000.0);
+        }
+        let uah = read_sysfs_u64(dir, charge_file)?;
+        voltage_v.map(|v| uah as f64 / 1_000_000.0 * v)
+    };
+
+    let design_wh = to_wh("energy_full_design", "charge_full_design").filter(|v| *v > 0.0);
+    let full_wh = to_wh("energy_full", "charge_full").filter(|v| *v > 0.0);
+    let now_wh = to_wh("energy_now", "charge_now");
+    let rate_w = read_sysfs_u64(dir, "power_now")
+        .map(|uw| uw as f64 / 1_000_000.0)
+        .or_else(|| {
+            let ua = read_sysfs_u64(dir, "current_now")?;
+            voltage_v.map(|v| ua as f64 / 1_000_000.0 * v)
+        })
+        .filter(|w| *w > 0.0);
+
+    let state = read_sysfs_string(dir, "status").unwrap_or_else(|| "Unknown".to_string());
+    let charge_percent = read_sysfs_u64(dir, "capacity")
+        .map(|c| c as f64)
+        .or_else(|| match (now_wh, full_wh) {
+            (Some(now), Some(full)) => Some((now / full * 100.0).round()),
+            _ => None,
+        });
+
+    let wear_percent = match (design_wh, full_wh) {
+        (Some(design), Some(full)) => {
+            Some((((1.0 - full / design) * 100.0).max(0.0) * 10.0).round() / 10.0)
+        }
+        _ => None,
+    };
+
+    let time_remaining_minutes = match (state.as_str(), now_wh, full_wh, rate_w) {
+        ("Discharging", Some(now), _, Some(rate)) => Some((now / rate * 60.0) as u64),
+        ("Charging", Some(now), Some(full), Some(rate)) => {
+            Some(((full - now).max(0.0) / rate * 60.0) as u64)
+        }
+        _ => None,
+    };
+
+    let mut battery = BatteryInfo {
+        name,
+        manufacturer: read_sysfs_string(dir, "manufacturer"),
+        model: read_sysfs_string(dir, "model_name"),
+        technology: read_sysfs_string(dir, "technology"),
+        charge_percent,
+        state,
+        design_capacity_wh: design_wh.map(|v| (v * 10.0).round() / 10.0),
+        full_capacity_wh: full_wh.map(|v| (v * 10.0).round() / 10.0),
+        wear_percent,
+        // Some firmware reports 0 when it doesn't track cycles.
+        cycle_count: read_sysfs_u64(dir, "cycle_count").filter(|c| *c > 0),
+        time_remaining_minutes,
+        health: String::new(),
+        warnings: Vec::new(),
+    };
+    apply_battery_health_rules(&mut battery);
+    battery
+}
+
+#[cfg(target_os = "linux")]
+fn apply_battery_health_rules(battery: &mut BatteryInfo) {
+    let mut warnings = Vec::new();
+    let wear = battery.wear_percent.unwrap_or(0.0);
+
+    if wear >= BATTERY_WEAR_REPLACE_PERCENT {
+        warnings.push(format!(
+            "Battery has lost {wear:.0}% of its design capacity; replacement recommended"
+        ));
+    } else if wear >= BATTERY_WEAR_WARN_PERCENT {
+        warnings.push(format!(
+            "Battery has lost {wear:.0}% of its design capacity"
+        ));
+    }
+    if let Some(cycles) = battery.cycle_count.filter(|c| *c >= BATTERY_CYCLE_WARN) {
+        warnings.push(format!("High cycle count ({cycles})"));
+    }
+    if battery.state == "Discharging"
+        && battery
+            .charge_percent
+            .map(|c| c <= BATTERY_LOW_PERCENT)
+            .unwrap_or(false)
+    {
+        warnings.push("Battery critically low and not charging".to_string());
+    }
+
+    battery.health = if wear >= BATTERY_WEAR_REPLACE_PERCENT {
+        "Replace".to_string()
+    } else if battery.wear_percent.is_none() {
+        "Unknown".to_string()
+    } else if !warnings.is_empty() {
+        "Fair".to_string()
+    } else {
+        "Good".to_string()
+    };
+    battery.warnings = warnings;
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_battery_status() -> Option<BatteryStatus> {
+    None
+}
+
+#[tauri::command]
+fn get_battery_status() -> Result<BatteryStatus, String> {
+    #[cfg(target_os = "linux")]
+    {
+        Ok(read_battery_status().unwrap_or_else(|| BatteryStatus {
+            timestamp: Utc::now().to_rfc3339(),
+            ..Default::default()
+        }))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("Battery report is only supported on Linux.".to_string())
+    }
+}
+
+//
+// ───────── Thermals & throttling ─────────
+//
+
+#[cfg(target_os = "linux")]
+const THERMAL_SAMPLE_INTERVAL_SECS: u64 = 10;
+#[cfg(target_os = "linux")]
+const THERMAL_MAX_EPISODES: usize = 20;
+// Running below this fraction of max frequency while hot counts as throttled.
+#[cfg(target_os = "linux")]
+const THROTTLE_FREQ_RATIO: f64 = 0.6;
+#[cfg(target_os = "linux")]
+const THROTTLE_HOT_CELSIUS: f32 = 80.0;
+
+#[derive(Serialize, Clone, Default)]
+struct TemperatureReading {
+    label: String,
+    source: String,
+    celsius: f32,
+    critical: Option<f32>,
+}
+
+#[derive(Serialize, Clone, Default)]
+struct FanReading {
+    label: String,
+    rpm: u64,
+}
+
+#[derive(Serialize, Clone, Default)]
+struct ThrottleEpisode {
+    started_at: String,
+    ended_at: Option<String>,
+    duration_secs: u64,
+    peak_celsius: Option<f32>,
+    min_frequency_mhz: Option<f64>,
+    reason: String,
+}
+
+#[derive(Serialize, Default)]
+struct ThermalStatus {
+    sensors: Vec<TemperatureReading>,
+    fans: Vec<FanReading>,
+    cpu_temperature_c: Option<f32>,
+    cpu_frequency_mhz: Option<f64>,
+    cpu_max_frequency_mhz: Option<f64>,
+    throttling_now: bool,
+    throttle_episodes: Vec<ThrottleEpisode>,
+    timestamp: String,
+}
+
+fn read_component_temperatures(system: &System) -> Vec<TemperatureReading> {
+    system
+        .components()
+        .iter()
+        .filter(|c| c.temperature().is_finite() && c.temperature() > 0.0)
+        .map(|c| TemperatureReading {
+            label: c.label().to_string(),
+            source: "sensor".to_string(),
+            celsius: c.temperature(),
+            critical: c.critical().filter(|v| v.is_finite() && *v > 0.0),
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn read_thermal_zones() -> Vec<TemperatureReading> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/thermal") else {
+        return Vec::new();
+    };
+    let mut zones = Vec::new();
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        if !entry
+            .file_name()
+            .to_string_lossy()
+            .starts_with("thermal_zone")
+        {
+            continue;
+        }
+        let Some(milli) = read_sysfs_string(&dir, "temp").and_then(|s| s.parse::<i64>().ok())
+        else {
+            continue;
+        };
+        if milli <= 0 {
+            continue;
+        }
+        // The first "critical" trip point, if the zone declares one.
+        let critical = (0..10).find_map(|i| {
+            let kind = read_sysfs_string(&dir, &format!("trip_point_{i}_type"))?;
+            if kind != "critical" {
+                return None;
+            }
+            read_sysfs_string(&dir, &format!("trip_point_{i}_temp"))
+                .and_then(|s| s.parse::<i64>().ok())
+                .map(|t| t as f32 / 1000.0)
+        });
+        zones.push(TemperatureReading {
+            label: read_sysfs_string(&dir, "type")
+                .unwrap_or_else(|| entry.file_name().to_string_lossy().to_string()),
+            source: "thermal_zone".to_string(),
+            celsius: milli as f32 / 1000.0,
+            critical,
+        });
+    }
+    zones
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_thermal_zones() -> Vec<TemperatureReading> {
+    Vec::new()
+}
+
+#[cfg(target_os = "linux")]
+fn read_hwmon_fans() -> Vec<FanReading> {
+    let Ok(entries) = std::fs::read_dir("/sys/class/hwmon") else {
+        return Vec::new();
+    };
+    let mut fans = Vec::new();
+    for entry in entries.flatten() {
+        let dir = entry.path();
+        let chip = read_sysfs_string(&dir, "name").unwrap_or_default();
+        let Ok(files) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for file in files.flatten() {
+            let file_name = file.file_name().to_string_lossy().to_string();
+            let Some(prefix) = file_name.strip_suffix("_input") else {
+                continue;
+            };
+            if !prefix.starts_with("fan") {
+                continue;
+            }
+            let Some(rpm) = read_sysfs_u64(&dir, &file_name) else {
+                continue;
+            };
+            let label = read_sysfs_string(&dir, &format!("{prefix}_label"))
+                .unwrap_or_else(|| format!("{chip} {prefix}").trim().to_string());
+            fans.push(FanReading { label, rpm });
+        }
+    }
+    fans.sort_by(|a, b| a.label.cmp(&b.label));
+    fans
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_hwmon_fans() -> Vec<FanReading> {
+    Vec::new()
+}
+
+/// Picks the reading most likely to be the CPU package; falls back to the hottest sensor.
+fn pick_cpu_temperature(readings: &[TemperatureReading]) -> Option<f32> {
+    const CPU_LABELS: [&str; 7] = [
+        "package id",
+        "tctl",
+        "tdie",
+        "coretemp",
+        "k10temp",
+        "x86_pkg_temp",
+        "cpu",
+    ];
+    let cpu_max = readings
+        .iter()
+        .filter(|r| {
+            let label = r.label.to_lowercase();
+            CPU_LABELS.iter().any(|key| label.contains(key))
+        })
+        .map(|r| r.celsius)
+        .fold(None, |acc: Option<f32>, t| {
+            Some(acc.map_or(t, |a| a.max(t)))
+        });
+    cpu_max.or_else(|| {
+        readings
+            .iter()
+            .map(|r| r.celsius)
+            .fold(None, |acc: Option<f32>, t| {
+                Some(acc.map_or(t, |a| a.max(t)))
+            })
+    })
+}
+
+/// (average current MHz, max MHz, cumulative kernel throttle events).
+#[cfg(target_os = "linux")]
+fn read_cpu_frequency() -> (Option<f64>, Option<f64>, Option<u64>) {
+    let Ok(entries) = std::fs::read_dir("/sys/devices/system/cpu") else {
+        return (None, None, None);
+    };
+    let mut current_sum = 0u64;
+    let mut current_count = 0u64;
+    let mut max_khz: Option<u64> = None;
+    let mut core_throttles: Option<u64> = None;
+    let mut package_throttles: Option<u64> = None;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let is_cpu_dir = name
+            .strip_prefix("cpu")
+            .map(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+            .unwrap_or(false);
+        if !is_cpu_dir {
+            continue;
+        }
+        let cpufreq = entry.path().join("cpufreq");
+        if let Some(cur) = read_sysfs_u64(&cpufreq, "scaling_cur_freq") {
+            current_sum += cur;
+            current_count += 1;
+        }
+        if let Some(max) = read_sysfs_u64(&cpufreq, "cpuinfo_max_freq") {
+            max_khz = Some(max_khz.map_or(max, |m| m.max(max)));
+        }
+        let throttle = entry.path().join("thermal_throttle");
+        if let Some(count) = read_sysfs_u64(&throttle, "core_throttle_count") {
+            core_throttles = Some(core_throttles.unwrap_or(0) + count);
+        }
+        // Package counters are mirrored on every core of the package.
+        if let Some(count) = read_sysfs_u64(&throttle, "package_throttle_count") {
+            package_throttles = Some(package_throttles.map_or(count, |p| p.max(count)));
+        }
+    }
+
+    let current_mhz =
+        (current_count > 0).then(|| current_sum as f64 / current_count as f64 / 1000.0);
+    let max_mhz = max_khz.map(|k| k as f64 / 1000.0);
+    let throttles = match (core_throttles, package_throttles) {
+        (None, None) => None,
+        (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+    };
+    (current_mhz, max_mhz, throttles)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_cpu_frequency() -> (Option<f64>, Option<f64>, Option<u64>) {
+    (None, None, None)
+}
+
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+struct ThermalHistory {
+    last_throttle_count: Option<u64>,
+    open_episode: Option<(std::time::Instant, ThrottleEpisode)>,
+    episodes: std::collections::VecDeque<ThrottleEpisode>,
+}
+
+#[cfg(target_os = "linux")]
+static THERMAL_HISTORY: once_cell::sync::Lazy<std::sync::Mutex<ThermalHistory>> =
+    once_cell::sync::Lazy::new(|| std::sync::Mutex::new(ThermalHistory::default()));
+
+#[cfg(target_os = "linux")]
+fn record_thermal_sample(
+    cpu_temp: Option<f32>,
+    current_mhz: Option<f64>,
+    max_mhz: Option<f64>,
+    throttle_count: Option<u64>,
+) {
+    let Ok(mut history) = THERMAL_HISTORY.lock() else {
+        return;
+    };
+
+    let kernel_throttled = match (history.last_throttle_count, throttle_count) {
+        (Some(prev), Some(now)) => now > prev,
+        _ => false,
+    };
+    history.last_throttle_count = throttle_count.or(history.last_throttle_count);
+
+    let hot = cpu_temp.map(|t| t >= THROTTLE_HOT_CELSIUS).unwrap_or(false);
+    let slow = match (current_mhz, max_mhz) {
+        (Some(cur), Some(max)) if max > 0.0 => cur / max < THROTTLE_FREQ_RATIO,
+        _ => false,
+    };
+    let throttled = kernel_throttled || (hot && slow);
+    let now = Utc::now().to_rfc3339();
+
+    if throttled {
+        let reason = if kernel_throttled {
+            "Kernel reported thermal throttling".to_string()
+        } else {
+            "CPU running well below max frequency while hot".to_string()
+        };
+        match history.open_episode.as_mut() {
+            Some((started, episode)) => {
+                episode.duration_secs = started.elapsed().as_secs();
+                if let Some(t) = cpu_temp {
+                    episode.peak_celsius = Some(episode.peak_celsius.map_or(t, |p| p.max(t)));
+                }
+                if let Some(mhz) = current_mhz {
+                    episode.min_frequency_mhz =
+                        Some(episode.min_frequency_mhz.map_or(mhz, |m| m.min(mhz)));
+                }
+            }
+            None => {
+                history.open_episode = Some((
+                    std::time::Instant::now(),
+                    ThrottleEpisode {
+                        started_at: now,
+                        ended_at: None,
+                        duration_secs: 0,
+                        peak_celsius: cpu_temp,
+                        min_frequency_mhz: current_mhz,
+                        reason,
+                    },
+                ));
+            }
+        }
+    } else if let Some((started, mut episode)) = history.open_episode.take() {
+        episode.duration_secs = started.elapsed().as_secs();
+        episode.ended_at = Some(now);
+        history.episodes.push_back(episode);
+        while history.episodes.len() > THERMAL_MAX_EPISODES {
+            history.episodes.pop_front();
+        }
+    }
+}
+
+/// Returns (throttling right now, recent episodes oldest first).
+#[cfg(target_os = "linux")]
+fn thermal_episodes() -> (bool, Vec<ThrottleEpisode>) {
+    let Ok(history) = THERMAL_HISTORY.lock() else {
+        return (false, Vec::new());
+    };
+    let mut episodes: Vec<ThrottleEpisode> = history.episodes.iter().cloned().collect();
+    if let Some((started, open)) = history.open_episode.as_ref() {
+        let mut open = open.clone();
+        open.duration_secs = started.elapsed().as_secs();
+        episodes.push(open);
+    }
+    (history.open_episode.is_some(), episodes)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn thermal_episodes() -> (bool, Vec<ThrottleEpisode>) {
+    (false, Vec::new())
+}
+
+#[cfg(target_os = "linux")]
+fn start_thermal_monitor() {
+    static THERMAL_STARTED: once_cell::sync::Lazy<std::sync::atomic::AtomicBool> =
+        once_cell::sync::Lazy::new(|| std::sync::atomic::AtomicBool::new(false));
+    if THERMAL_STARTED.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        return;
+    }
+
+    std::thread::spawn(|| {
+        let mut system = System::new();
+        system.refresh_components_list();
+        loop {
+            system.refresh_components();
+            let mut readings = read_component_temperatures(&system);
+            readings.extend(read_thermal_zones());
+            let (current_mhz, max_mhz, throttle_count) = read_cpu_frequency();
+            record_thermal_sample(
+                pick_cpu_temperature(&readings),
+                current_mhz,
+                max_mhz,
+                throttle_count,
+            );
+            sleep(Duration::from_secs(THERMAL_SAMPLE_INTERVAL_SECS));
+        }
+    });
+}
+
+#[cfg(not(target_os = "linux"))]
+fn start_thermal_monitor() {}
+
+#[tauri::command]
+fn get_thermal_status() -> Result<ThermalStatus, String> {
+    let mut system = System::new();
+    system.refresh_components_list();
+
+    let mut sensors = read_component_temperatures(&system);
+    sensors.extend(read_thermal_zones());
+    let cpu_temperature_c = pick_cpu_temperature(&sensors);
+    let (cpu_frequency_mhz, cpu_max_frequency_mhz, _) = read_cpu_frequency();
+    let (throttling_now, throttle_episodes) = thermal_episodes();
+
+    Ok(ThermalStatus {
+        sensors,
+        fans: read_hwmon_fans(),
+        cpu_temperature_c,
+        cpu_frequency_mhz,
+        cpu_max_frequency_mhz,
+        throttling_now,
+        throttle_episodes,
+        timestamp: Utc::now().to_rfc3339(),
+    })
+}
+
+//
+// ───────── Memory pressure ─────────
+//
+
+const MEMORY_TOP_PROCESSES: usize = 8;
+#[cfg(target_os = "linux")]
+const MEMORY_OOM_LOOKBACK: &str = "-24h";
+
+#[derive(Serialize, Clone, Default)]
+struct ProcessMemory {
+    name: String,
+    pid: u32,
+    process_count: usize,
+    memory_mb: f64,
+}
+
+#[derive(Serialize, Clone, Default)]
+struct OomEvent {
+    timestamp: Option<String>,
+    process: Option<String>,
+    pid: Option<u32>,
+    message: String,
+}
+
+#[derive(Serialize, Default)]
+struct MemoryPressure {
+    // "ok", "under_pressure" or "thrashing"
+    verdict: String,
+    reasons: Vec<String>,
+    memory_used_gb: f64,
+    memory_available_gb: f64,
+    memory_total_gb: f64,
+    swap_used_gb: f64,
+    swap_total_gb: f64,
+    page_in_kb_per_sec: Option<f64>,
+    page_out_kb_per_sec: Option<f64>,
+    swap_in_pages_per_sec: Option<f64>,
+    swap_out_pages_per_sec: Option<f64>,
+    psi_some_avg10: Option<f64>,
+    psi_some_avg60: Option<f64>,
+    psi_full_avg10: Option<f64>,
+    psi_full_avg60: Option<f64>,
+    oom_kills: Vec<OomEvent>,
+    top_processes: Vec<ProcessMemory>,
+    timestamp: String,
+}
+
+/// Cumulative (pgpgin, pgpgout, pswpin, pswpout) from /proc/vmstat.
+#[cfg(target_os = "linux")]
+fn read_vmstat_paging() -> Option<(u64, u64, u64, u64)> {
+    let text = std::fs::read_to_string("/proc/vmstat").ok()?;
+    let mut values = [None; 4];
+    for line in text.lines() {
+        let mut parts = line.split_whitespace();
+        let (Some(key), Some(value)) = (parts.next(), parts.next()) else {
+            continue;
+        };
+        let slot = match key {
+            "pgpgin" => 0,
+            "pgpgout" => 1,
+            "pswpin" => 2,
+            "pswpout" => 3,
+            _ => continue,
+        };
+        values[slot] = value.parse::<u64>().ok();
+    }
+    Some((values[0]?, values[1]?, values[2]?, values[3]?))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_vmstat_paging() -> Option<(u64, u64, u64, u64)> {
+    None
+}
+
+/// Parses `/proc/pressure/memory` into ((some avg10, some avg60), (full avg10, full avg60)).
+#[cfg(target_os = "linux")]
+fn read_memory_psi() -> Option<((f64, f64), (f64, f64))> {
+    let text = std::fs::read_to_string("/proc/pressure/memory").ok()?;
+    let parse_line = |prefix: &str| -> Option<(f64, f64)> {
+        let line = text.lines().find(|l| l.starts_with(prefix))?;
+        let field = |name: &str| -> Option<f64> {
+            line.split_whitespace()
+                .find_map(|kv| kv.strip_prefix(name))
+                .and_then(|v| v.parse().ok())
+        };
+        Some((field("avg10=")?, field("avg60=")?))
+    };
+    Some((parse_line("some")?, parse_line("full")?))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_memory_psi() -> Option<((f64, f64), (f64, f64))> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn read_oom_events() -> Vec<OomEvent> {
+    parse_oom_events(&read_kernel_log(&["--since", MEMORY_OOM_LOOKBACK]))
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_oom_events() -> Vec<OomEvent> {
+    Vec::new()
+}
+
+#[cfg(target_os = "linux")]
+fn parse_oom_events(log: &str) -> Vec<OomEvent> {
+    let Ok(killed) = regex::Regex::new(r"Killed process (\d+) \(([^)]+)\)") else {
+        return Vec::new();
+    };
+    // Both the global and the memory-cgroup OOM killer log "Killed process <pid> (<name>)".
+    log.lines()
+        .filter(|line| killed.is_match(line))
+        .map(|line| {
+            let caps = killed.captures(line);
+            let timestamp = line
+                .split_whitespace()
+                .next()
+                .filter(|t| t.len() >= 19 && t.as_bytes()[4] == b'-')
+                .map(|t| t.to_string());
+            OomEvent {
+                timestamp,
+                process: caps
+                    .as_ref()
+                    .and_then(|c| c.get(2))
+                    .map(|m| m.as_str().to_string()),
+                pid: caps
+                    .as_ref()
+                    .and_then(|c| c.get(1))
+                    .and_then(|m| m.as_str().parse().ok()),
+                message: line.trim().to_string(),
+            }
+        })
+        .collect()
+}
+
+fn top_memory_processes(system: &System, limit: usize) -> Vec<ProcessMemory> {
+    // Multi-process apps (browsers, Teams) are grouped by name so they rank as one.
+    let mut by_name: std::collections::HashMap<String, ProcessMemory> =
+        std::collections::HashMap::new();
+    for (pid, process) in system.processes() {
+        let entry = by_name
+            .entry(process.name().to_string())
+            .or_insert_with(|| ProcessMemory {
+                name: process.name().to_string(),
+                pid: pid.as_u32(),
+                ..Default::default()
+            });
+        entry.memory_mb += process.memory() as f64 / 1024.0 / 1024.0;
+        entry.process_count += 1;
+    }
+    let mut items: Vec<ProcessMemory> = by_name.into_values().collect();
+    for item in items.iter_mut() {
+        item.memory_mb = (item.memory_mb * 10.0).round() / 10.0;
+    }
+    items.sort_by(|a, b| {
+        b.memory_mb
+            .partial_cmp(&a.memory_mb)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    items.truncate(limit);
+    items
+}
+
+fn memory_verdict(report: &MemoryPressure) -> (String, Vec<String>) {
+    let mut reasons = Vec::new();
+    let swap_in = report.swap_in_pages_per_sec.unwrap_or(0.0);
+    let swap_out = report.swap_out_pages_per_sec.unwrap_or(0.0);
+    let full10 = report.psi_full_avg10.unwrap_or(0.0);
+    let some10 = report.psi_some_avg10.unwrap_or(0.0);
+
+    // Thrashing: tasks fully stalled on memory, or heavy swapping both ways.
+    let thrashing = full10 >= 10.0 || (swap_in >= 200.0 && swap_out >= 200.0);
+    if full10 >= 10.0 {
+        reasons.push(format!(
+            "All tasks stalled on memory {full10:.0}% of the last 10s"
+        ));
+    }
+    if swap_in >= 200.0 && swap_out >= 200.0 {
+        reasons.push(format!(
+            "Swapping heavily ({swap_in:.0} pages/s in, {swap_out:.0} pages/s out)"
+        ));
+    }
+
+    let available_pct = if report.memory_total_gb > 0.0 {
+        report.memory_available_gb / report.memory_total_gb * 100.0
+    } else {
+        100.0
+    };
+    if some10 >= 10.0 {
+        reasons.push(format!(
+            "Tasks waited on memory {some10:.0}% of the last 10s"
+        ));
+    }
+    if available_pct < 10.0 {
+        reasons.push(format!("Only {available_pct:.0}% of RAM available"));
+    }
+    if swap_in > 0.0
+        && report.swap_total_gb > 0.0
+        && report.swap_used_gb / report.swap_total_gb > 0.5
+    {
+        reasons.push("Swap more than half full and being read back".to_string());
+    }
+    if !report.oom_kills.is_empty() {
+        reasons.push(format!(
+            "{} process(es) killed by the OOM killer recently",
+            report.oom_kills.len()
+        ));
+    }
+
+    let verdict = if thrashing {
+        "thrashing"
+    } else if reasons.is_empty() {
+        "ok"
+    } else {
+        "under_pressure"
+    };
+    (verdict.to_string(), reasons)
+}
+
+fn memory_pressure_report() -> MemoryPressure {
+    let mut system = System::new();
+    system.refresh_memory();
+    system.refresh_processes();
+
+    let paging_before = read_vmstat_paging();
+    let started = std::time::Instant::now();
+    sleep(Duration::from_secs(1));
+    let paging_after = read_vmstat_paging();
+    let secs = started.elapsed().as_secs_f64();
+    let rate = |a: u64, b: u64| b.saturating_sub(a) as f64 / secs;
+    let paging = match (paging_before, paging_after) {
+        (Some(a), Some(b)) if secs > 0.0 => Some((
+            rate(a.0, b.0),
+            rate(a.1, b.1),
+            rate(a.2, b.2),
+            rate(a.3, b.3),
+        )),
+        _ => None,
+    };
+    let psi = read_memory_psi();
+
+    let mut report = MemoryPressure {
+        memory_used_gb: bytes_to_gb(system.used_memory()),
+        memory_available_gb: bytes_to_gb(system.available_memory()),
+        memory_total_gb: bytes_to_gb(system.total_memory()),
+        swap_used_gb: bytes_to_gb(system.used_swap()),
+        swap_total_gb: bytes_to_gb(system.total_swap()),
+        page_in_kb_per_sec: paging.map(|p| p.0),
+        page_out_kb_per_sec: paging.map(|p| p.1),
+        swap_in_pages_per_sec: paging.map(|p| p.2),
+        swap_out_pages_per_sec: paging.map(|p| p.3),
+        psi_some_avg10: psi.map(|p| p.0 .0),
+        psi_some_avg60: psi.map(|p| p.0 .1),
+        psi_full_avg10: psi.map(|p| p.1 .0),
+        psi_full_avg60: psi.map(|p| p.1 .1),
+        oom_kills: read_oom_events(),
+        top_processes: top_memory_processes(&system, MEMORY_TOP_PROCESSES),
+        timestamp: Utc::now().to_rfc3339(),
+        ..Default::default()
+    };
+    let (verdict, reasons) = memory_verdict(&report);
+    report.verdict = verdict;
+    report.reasons = reasons;
+    report
+}
+
+#[tauri::command]
+async fn get_memory_pressure() -> Result<MemoryPressure, String> {
+    tauri::async_runtime::spawn_blocking(|| Ok(memory_pressure_report()))
+        .await
+        .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+//
+// ───────── Disk encryption ─────────
+//
+
+#[derive(Serialize, Clone, Default)]
+struct EncryptionVolume {
+    volume: String,
+    device: Option<String>,
+    // "BitLocker", "LUKS2", "LUKS1", "dm-crypt", "eCryptfs", "fscrypt" or "None"
+    method: String,
+    protected: bool,
+    lock_status: Option<String>,
+    encryption_percentage: Option<f64>,
+}
+
+#[derive(Serialize, Clone, Default)]
+struct EncryptionStatus {
+    system_volume_encrypted: Option<bool>,
+    home_encrypted: Option<bool>,
+    volumes: Vec<EncryptionVolume>,
+}
+
+#[cfg(target_os = "windows")]
+fn get_encryption_status_impl() -> EncryptionStatus {
+    encryption_from_bitlocker(&get_bitlocker_status())
+}
+
+#[cfg(target_os = "windows")]
+fn encryption_from_bitlocker(bitlocker: &[BitlockerVolume]) -> EncryptionStatus {
+    let system_mount = system_volume_mount();
+    let volumes: Vec<EncryptionVolume> = bitlocker
+        .iter()
+        .map(|v| EncryptionVolume {
+            volume: v.volume.clone(),
+            device: None,
+            method: if v.protection_status == "On" {
+                "BitLocker".to_string()
+            } else {
+                "None".to_string()
+            },
+            protected: v.protection_status == "On",
+            lock_status: Some(v.lock_status.clone()),
+            encryption_percentage: v.encryption_percentage,
+        })
+        .collect();
+    let system_volume_encrypted = volumes
+        .iter()
+        .find(|v| same_mount(&v.volume, &system_mount))
+        .map(|v| v.protected);
+
+    EncryptionStatus {
+        system_volume_encrypted,
+        // Profiles live on the system volume unless redirected.
+        home_encrypted: system_volume_encrypted,
+        volumes,
+    }
+}
+
+/// Encryption type of a device-mapper target from `/sys/block/<dm>/dm/uuid`
+/// (cryptsetup prefixes its mappings with `CRYPT-<TYPE>-`).
+#[cfg(target_os = "linux")]
+fn dm_crypt_method(kname: &str) -> Option<String> {
+    let uuid = std::fs::read_to_string(format!("/sys/block/{kname}/dm/uuid")).ok()?;
+    let kind = uuid.trim().strip_prefix("CRYPT-")?.split('-').next()?;
+    Some(match kind {
+        "LUKS1" | "LUKS2" => kind.to_string(),
+        "PLAIN" => "dm-crypt".to_string(),
+        "BITLK" => "BitLocker".to_string(),
+        "TCRYPT" => "VeraCrypt".to_string(),
+        other => format!("dm-crypt ({other})"),
+    })
+}
+
+/// Walks the device-mapper stack below `kname` (LVM on LUKS, etc.) looking for
+/// a crypt target.
+#[cfg(target_os = "linux")]
+fn find_crypt_layer(kname: &str, depth: u32) -> Option<String> {
+    if depth > 8 {
+        return None;
+    }
+    if let Some(method) = dm_crypt_method(kname) {
+        return Some(method);
+    }
+    let slaves = std::fs::read_dir(format!("/sys/class/block/{kname}/slaves")).ok()?;
+    slaves
+        .flatten()
+        .find_map(|s| find_crypt_layer(&s.file_name().to_string_lossy(), depth + 1))
+}
+
+#[cfg(target_os = "linux")]
+fn home_directory_encryption(home: &str) -> Option<String> {
+    let mounts = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+    let ecryptfs_mounted = mounts.lines().any(|line| {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        fields.len() > 2 && fields[2] == "ecryptfs" && unescape_mount_path(fields[1]) == home
+    });
+    let user = std::path::Path::new(home)
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    if ecryptfs_mounted || std::path::Path::new("/home/.ecryptfs").join(&user).exists() {
+        return Some("eCryptfs".to_string());
+    }
+
+    // fscrypt marks encrypted directories with the `E` attribute.
+    let lsattr = Command::new("lsattr").args(["-d", home]).output().ok();
+    if let Some(out) = lsattr.filter(|o| o.status.success()) {
+        let text = String::from_utf8_lossy(&out.stdout);
+        if let Some(flags) = text.split_whitespace().next() {
+            if flags.contains('E') {
+                return Some("fscrypt".to_string());
+            }
+        }
+    }
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn get_encryption_status_impl() -> EncryptionStatus {
+    let mounts = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+    let mut entries: Vec<(String, String)> = mounts
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 2 || !fields[0].starts_with("/dev/") {
+                return None;
+            }
+            Some((fields[0].to_string(), unescape_mount_path(fields[1])))
+        })
+        .collect();
+    // Shortest mount first so "/" wins over bind mounts and subvolumes of the same device.
+    entries.sort_by_key(|(_, mount)| mount.len());
+
+    let mut seen_devices: Vec<String> = Vec::new();
+    let mut volumes: Vec<EncryptionVolume> = Vec::new();
+    for (device, mount) in entries {
+        let kname = std::fs::canonicalize(&device)
+            .ok()
+            .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
+            .unwrap_or_else(|| device.trim_start_matches("/dev/").to_string());
+        if kname.starts_with("loop") || seen_devices.contains(&kname) {
+            continue;
+        }
+        seen_devices.push(kname.clone());
+
+        let method = find_crypt_layer(&kname, 0);
+        volumes.push(EncryptionVolume {
+            volume: mount,
+            device: Some(device),
+            protected: method.is_some(),
+            method: method.unwrap_or_else(|| "None".to_string()),
+            lock_status: Some("Unlocked".to_string()),
+            encryption_percentage: None,
+        });
+    }
+
+    // LUKS containers that are present but not opened don't show up in /proc/mounts.
+    if let Ok(out) = Command::new("lsblk")
+        .args(["--json", "--list", "-o", "KNAME,FSTYPE"])
+        .output()
+    {
+        #[derive(Deserialize)]
+        struct LsblkDevice {
+            kname: Option<String>,
+            fstype: Option<String>,
+        }
+        #[derive(Deserialize)]
+        struct LsblkOutput {
+            blockdevices: Vec<LsblkDevice>,
+        }
+        if let Ok(parsed) = serde_json::from_slice::<LsblkOutput>(&out.stdout) {
+            for dev in parsed.blockdevices {
+                let (Some(kname), Some("crypto_LUKS")) = (dev.kname, dev.fstype.as_deref()) else {
+                    continue;
+                };
+                let holders = std::fs::read_dir(format!("/sys/class/block/{kname}/holders"))
+                    .map(|d| d.count())
+                    .unwrap_or(0);
+                if holders == 0 {
+                    volumes.push(EncryptionVolume {
+                        volume: format!("/dev/{kname}"),
+                        device: Some(format!("/dev/{kname}")),
+                        method: "LUKS".to_string(),
+                        protected: true,
+                        lock_status: Some("Locked".to_string()),
+                        encryption_percentage: None,
+                    });
+                }
+            }
+        }
+    }
+
+    let system_volume_encrypted = volumes
+        .iter()
+        .find(|v| v.volume == "/")
+        .map(|v| v.protected);
+
+    let home_encrypted = std::env::var("HOME").ok().map(|home| {
+        if let Some(method) = home_directory_encryption(&home) {
+            volumes.push(EncryptionVolume {
+                volume: home.clone(),
+                device: None,
+                method,
+                protected: true,
+                lock_status: Some("Unlocked".to_string()),
+                encryption_percentage: None,
+            });
+            return true;
+        }
+        // Otherwise the home directory inherits whatever volume it lives on.
+        volumes
+            .iter()
+            .filter(|v| v.device.is_some() && std::path::Path::new(&home).starts_with(&v.volume))
+            .max_by_key(|v| v.volume.len())
+            .map(|v| v.protected)
+            .unwrap_or(false)
+    });
+
+    EncryptionStatus {
+        system_volume_encrypted,
+        home_encrypted,
+        volumes,
+    }
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn get_encryption_status_impl() -> EncryptionStatus {
+    EncryptionStatus::default()
+}
+
+#[tauri::command]
+fn get_encryption_status() -> Result<EncryptionStatus, String> {
+    Ok(get_encryption_status_impl())
+}
+
+//
+// ───────── Antivirus catalog ─────────
+//
+
+// Shipped catalog; a remotely refreshed copy in app-local data takes precedence.
+const AV_CATALOG_DEFAULT: &str = include_str!("../resources/av-catalog.json");
+const AV_CATALOG_FILE: &str = "av-catalog.json";
+const AV_CATALOG_URL_ENV: &str = "GOLPAC_AV_CATALOG_URL";
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct AvCatalog {
+    version: u32,
+    products: Vec<AvCatalogEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct AvCatalogEntry {
+    name: String,
+    // Lower-case fragments used to match the product name the UI passes back.
+    aliases: Vec<String>,
+    // "windows", "linux" or "macos"; empty means every platform.
+    platforms: Vec<String>,
+    processes: Vec<String>,
+    services: Vec<String>,
+    install_paths: Vec<String>,
+    last_scan: Option<AvCatalogSource>,
+    definitions: Option<AvCatalogSource>,
+    status_command: Option<AvStatusCommand>,
+    launch: Vec<AvLaunchCommand>,
+}
+
+/// Either a registry value (Windows) or a file/directory whose modification
+/// time is used.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct AvCatalogSource {
+    registry_key: Option<String>,
+    registry_value: Option<String>,
+    path: Option<String>,
+}
+
+/// A status tool printing `key : value` lines, e.g. `mdatp health`.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct AvStatusCommand {
+    program: String,
+    args: Vec<String>,
+    running_when_true: Vec<String>,
+    definitions_age_minutes_field: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct AvLaunchCommand {
+    program: String,
+    args: Vec<String>,
+}
+
+fn current_platform() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "windows"
+    } else if cfg!(target_os = "macos") {
+        "macos"
+    } else {
+        "linux"
+    }
+}
+
+impl AvCatalog {
+    fn for_platform<'a>(&'a self, platform: &'a str) -> impl Iterator<Item = &'a AvCatalogEntry> {
+        self.products.iter().filter(move |p| {
+            p.platforms.is_empty() || p.platforms.iter().any(|x| x.eq_ignore_ascii_case(platform))
+        })
+    }
+
+    fn find<'a>(&'a self, product: &str, platform: &'a str) -> Option<&'a AvCatalogEntry> {
+        let needle = product.trim().to_lowercase();
+        if needle.is_empty() {
+            return None;
+        }
+        self.for_platform(platform).find(|entry| {
+            entry.name.to_lowercase() == needle
+                || entry
+                    .aliases
+                    .iter()
+                    .any(|alias| !alias.is_empty() && needle.contains(&alias.to_lowercase()))
+        })
+    }
+}
+
+fn parse_av_catalog(text: &str) -> Result<AvCatalog, String> {
+    let catalog: AvCatalog =
+        serde_json::from_str(text).map_err(|e| format!("Invalid antivirus catalog: {e}"))?;
+    if catalog.products.is_empty() {
+        return Err("Antivirus catalog has no products".to_string());
+    }
+    if let Some(entry) = catalog.products.iter().find(|p| p.name.trim().is_empty()) {
+        return Err(format!(
+            "Antivirus catalog entry without a name (processes: {:?})",
+            entry.processes
+        ));
+    }
+    Ok(catalog)
+}
+
+/// Process names compare case-insensitively without `.exe`; Linux truncates
+/// names to 15 bytes (TASK_COMM_LEN - 1), so a truncated prefix also matches.
+fn process_name_matches(actual: &str, wanted: &str) -> bool {
+    let normalize = |s: &str| {
+        let lower = s.trim().to_lowercase();
+        lower.strip_suffix(".exe").unwrap_or(&lower).to_string()
+    };
+    let actual = normalize(actual);
+    let wanted = normalize(wanted);
+    !wanted.is_empty() && (actual == wanted || (actual.len() == 15 && wanted.starts_with(&actual)))
+}
+
+#[cfg(target_os = "linux")]
+fn catalog_process_running(entry: &AvCatalogEntry, process_names: &[String]) -> bool {
+    entry.processes.iter().any(|wanted| {
+        process_names
+            .iter()
+            .any(|actual| process_name_matches(actual, wanted))
+    })
+}
+
+/// Reads `key : value` pairs from a status tool's output.
+#[cfg(target_os = "linux")]
+fn parse_status_fields(output: &str) -> std::collections::HashMap<String, String> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (k, v) = line.split_once(':')?;
+            Some((k.trim().to_string(), v.trim().trim_matches('"').to_string()))
+        })
+        .collect()
+}
+
+fn av_catalog_cache_path(app: &AppHandle) -> Option<std::path::PathBuf> {
+    app.path()
+        .app_local_data_dir()
+        .ok()
+        .map(|dir| dir.join(AV_CATALOG_FILE))
+}
+
+/// Downloaded catalogs may add detection rules, but the commands they name are
+/// only kept when the bundled catalog runs the same program with the same
+/// arguments, so a catalog server cannot make the app execute anything new.
+fn restrict_av_commands(mut catalog: AvCatalog, bundled: &AvCatalog) -> AvCatalog {
+    let launch_allowed = |cmd: &AvLaunchCommand| {
+        bundled.products.iter().any(|p| {
+            p.launch
+                .iter()
+                .any(|b| b.program == cmd.program && b.args == cmd.args)
+        })
+    };
+    let status_allowed = |cmd: &AvStatusCommand| {
+        bundled.products.iter().any(|p| {
+            p.status_command
+                .as_ref()
+                .map(|b| b.program == cmd.program && b.args == cmd.args)
+                .unwrap_or(false)
+        })
+    };
+    for entry in &mut catalog.products {
+        entry.launch.retain(|cmd| launch_allowed(cmd));
+        if !entry
+            .status_command
+            .as_ref()
+            .map(status_allowed)
+            .unwrap_or(true)
+        {
+            entry.status_command = None;
+        }
+    }
+    catalog
+}
+
+/// Catalog URLs must be https; redirects to plain http are refused as well.
+fn download_catalog(url: &str, what: &str) -> Result<String, String> {
+    if !url.trim().to_lowercase().starts_with("https://") {
+        return Err(format!("Refusing to download {what} over a non-https URL"));
+    }
+    Client::builder()
+        .timeout(Duration::from_secs(15))
+        .https_only(true)
+        .build()
+        .map_err(|e| format!("Failed to build client: {e}"))?
+        .get(url.trim())
+        .send()
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to download {what}: {e}"))?
+        .text()
+        .map_err(|e| format!("Failed to read {what}: {e}"))
+}
+
+fn load_av_catalog(app: &AppHandle) -> AvCatalog {
+    let bundled = parse_av_catalog(AV_CATALOG_DEFAULT).unwrap_or_default();
+    if let Some(cached) = av_catalog_cache_path(app)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|text| parse_av_catalog(&text).ok())
+    {
+        return restrict_av_commands(cached, &bundled);
+    }
+    bundled
+}
+
+fn refresh_av_catalog_impl(app: &AppHandle) -> Result<AvCatalog, String> {
+    let url = std::env::var(AV_CATALOG_URL_ENV)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .ok_or_else(|| format!("{AV_CATALOG_URL_ENV} is not set"))?;
+    let text = download_catalog(&url, "antivirus catalog")?;
+
+    // Validate before replacing the cached copy.
+    let catalog = restrict_av_commands(
+        parse_av_catalog(&text)?,
+        &parse_av_catalog(AV_CATALOG_DEFAULT).unwrap_or_default(),
+    );
+    let path = av_catalog_cache_path(app).ok_or_else(|| "No app data dir".to_string())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, text).map_err(|e| e.to_string())?;
+    Ok(catalog)
+}
+
+fn start_av_catalog_refresh(app: &AppHandle) {
+    if std::env::var(AV_CATALOG_URL_ENV).is_err() {
+        return;
+    }
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        if let Err(err) = refresh_av_catalog_impl(&app_handle) {
+            eprintln!("Antivirus catalog refresh failed: {err}");
+        }
+    });
+}
+
+#[tauri::command]
+async fn refresh_av_catalog(app: AppHandle) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        refresh_av_catalog_impl(&app).map(|catalog| catalog.products.len())
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+#[cfg(target_os = "linux")]
+fn systemd_unit_active(unit: &str) -> bool {
+    Command::new("systemctl")
+        .args(["is-active", "--quiet", unit])
+        .status()
+        .map(|s| s.success())
+        .unwrap_or(false)
+}
+
+/// Age in days of `path`, or of the newest file inside it when it is a directory.
+#[cfg(target_os = "linux")]
+fn path_age_days(path: &str) -> Option<f64> {
+    let meta = std::fs::metadata(path).ok()?;
+    let newest = if meta.is_dir() {
+        std::fs::read_dir(path)
+            .ok()?
+            .flatten()
+            .filter_map(|e| e.metadata().ok())
+            .filter(|m| m.is_file())
+            .filter_map(|m| m.modified().ok())
+            .max()?
+    } else {
+        meta.modified().ok()?
+    };
+    let age = std::time::SystemTime::now().duration_since(newest).ok()?;
+    Some((age.as_secs_f64() / 86_400.0 * 10.0).round() / 10.0)
+}
+
+#[cfg(target_os = "linux")]
+fn run_av_status_command(entry: &AvCatalogEntry, status: &AvStatusCommand) -> Option<AvProduct> {
+    let output = Command::new(&status.program)
+        .args(&status.args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let fields = parse_status_fields(&String::from_utf8_lossy(&output.stdout));
+    let running = !status.running_when_true.is_empty()
+        && status
+            .running_when_true
+            .iter()
+            .all(|key| fields.get(key).map(|v| v == "true").unwrap_or(false));
+    let definitions_age_days = status
+        .definitions_age_minutes_field
+        .as_ref()
+        .and_then(|key| fields.get(key))
+        .and_then(|v| v.parse::<f64>().ok())
+        .map(|mins| (mins / 1440.0 * 10.0).round() / 10.0);
+
+    Some(AvProduct {
+        name: entry.name.clone(),
+        running,
+        last_scan: None,
+        definitions_age_days,
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn detect_catalog_products_linux(catalog: &AvCatalog) -> Vec<AvProduct> {
+    let mut system = System::new();
+    system.refresh_processes();
+    let process_names: Vec<String> = system
+        .processes()
+        .values()
+        .map(|p| p.name().to_string())
+        .collect();
+
+    let mut products: Vec<AvProduct> = Vec::new();
+    for entry in catalog.for_platform("linux") {
+        if let Some(product) = entry
+            .status_command
+            .as_ref()
+            .and_then(|status| run_av_status_command(entry, status))
+        {
+            products.push(product);
+            continue;
+        }
+
+        let process_running = catalog_process_running(entry, &process_names);
+        let installed = process_running
+            || entry
+                .install_paths
+                .iter()
+                .any(|p| std::path::Path::new(p).exists());
+        if !installed {
+            continue;
+        }
+        let running =
+            process_running || entry.services.iter().any(|unit| systemd_unit_active(unit));
+
+        let last_scan = entry
+            .last_scan
+            .as_ref()
+            .and_then(|src| src.path.as_deref())
+            .and_then(|p| std::fs::metadata(p).ok())
+            .and_then(|m| m.modified().ok())
+            .map(|t| chrono::DateTime::<Utc>::from(t).to_rfc3339());
+
+        products.push(AvProduct {
+            name: entry.name.clone(),
+            running,
+            last_scan,
+            definitions_age_days: entry
+                .definitions
+                .as_ref()
+                .and_then(|src| src.path.as_deref())
+                .and_then(path_age_days),
+        });
+    }
+    products
+}
+
+//
+// ───────── Linux drivers & firmware ─────────
+//
+
+#[derive(Serialize, Default, Clone)]
+struct DriverIssue {
+    // "unbound_device", "tainted_module", "firmware_load_failure" or "firmware_update".
+    kind: String,
+    device: String,
+    detail: String,
+    timestamp: Option<String>,
+}
+
+impl DriverIssue {
+    // Same shape the Windows check reports, so the UI can list either.
+    #[cfg(target_os = "linux")]
+    fn as_entry(&self) -> DriverEntry {
+        DriverEntry {
+            device: self.device.clone(),
+            version: self.detail.clone(),
+            date: self.timestamp.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Kernel ring buffer for the given journalctl range, falling back to dmesg.
+#[cfg(target_os = "linux")]
+fn read_kernel_log(range: &[&str]) -> String {
+    let output = Command::new("journalctl")
+        .args(["-k", "--no-pager", "-o", "short-iso"])
+        .args(range)
+        .output();
+    match output {
+        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout).to_string(),
+        // No journal access: fall back to the ring buffer (may be restricted too).
+        _ => Command::new("dmesg")
+            .args(["--time-format", "iso"])
+            .output()
+            .map(|out| String::from_utf8_lossy(&out.stdout).to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// `lspci -Dmm` slot -> "Vendor Device", for readable names.
+#[cfg(target_os = "linux")]
+fn pci_device_names() -> std::collections::HashMap<String, String> {
+    let Ok(output) = Command::new("lspci").arg("-Dmm").output() else {
+        return std::collections::HashMap::new();
+    };
+    let Ok(field) = regex::Regex::new(r#""([^"]*)""#) else {
+        return std::collections::HashMap::new();
+    };
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let (slot, rest) = line.split_once(' ')?;
+            let fields: Vec<&str> = field
+                .captures_iter(rest)
+                .filter_map(|c| c.get(1).map(|m| m.as_str()))
+                .collect();
+            // "class" "vendor" "device" ...
+            let name = format!("{} {}", fields.get(1)?, fields.get(2)?);
+            Some((slot.to_string(), name))
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn unbound_pci_devices() -> Vec<DriverIssue> {
+    let Ok(entries) = std::fs::read_dir("/sys/bus/pci/devices") else {
+        return Vec::new();
+    };
+    let names = pci_device_names();
+    let mut issues = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.join("driver").exists() {
+            continue;
+        }
+        let class = read_sysfs_string(&path, "class").unwrap_or_default();
+        // Host/ISA bridges routinely run without a driver.
+        if class.starts_with("0x06") {
+            continue;
+        }
+        let slot = entry.file_name().to_string_lossy().to_string();
+        let ids = format!(
+            "{}:{}",
+            read_sysfs_string(&path, "vendor")
+                .unwrap_or_default()
+                .trim_start_matches("0x"),
+            read_sysfs_string(&path, "device")
+                .unwrap_or_default()
+                .trim_start_matches("0x")
+        );
+        let name = names.get(&slot).cloned().unwrap_or_else(|| ids.clone());
+        issues.push(DriverIssue {
+            kind: "unbound_device".to_string(),
+            device: format!("PCI {slot} {name}"),
+            detail: format!("No driver bound ({ids}, class {class})"),
+            timestamp: None,
+        });
+    }
+    issues
+}
+
+#[cfg(target_os = "linux")]
+fn unbound_usb_devices() -> Vec<DriverIssue> {
+    let Ok(entries) = std::fs::read_dir("/sys/bus/usb/devices") else {
+        return Vec::new();
+    };
+    let mut seen = std::collections::HashSet::new();
+    let mut issues = Vec::new();
+    for entry in entries.flatten() {
+        // Drivers bind to interfaces ("1-2:1.0"), not to the device node itself.
+        let iface = entry.file_name().to_string_lossy().to_string();
+        let Some((device_id, _)) = iface.split_once(':') else {
+            continue;
+        };
+        let path = entry.path();
+        if path.join("driver").exists() || !seen.insert(device_id.to_string()) {
+            continue;
+        }
+        let device_path = std::path::Path::new("/sys/bus/usb/devices").join(device_id);
+        let ids = format!(
+            "{}:{}",
+            read_sysfs_string(&device_path, "idVendor").unwrap_or_default(),
+            read_sysfs_string(&device_path, "idProduct").unwrap_or_default()
+        );
+        let name = [
+            read_sysfs_string(&device_path, "manufacturer"),
+            read_sysfs_string(&device_path, "product"),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join(" ");
+        issues.push(DriverIssue {
+            kind: "unbound_device".to_string(),
+            device: format!(
+                "USB {device_id} {}",
+                if name.is_empty() { &ids } else { &name }
+            ),
+            detail: format!(
+                "No driver bound ({ids}, interface class {})",
+                read_sysfs_string(&path, "bInterfaceClass").unwrap_or_default()
+            ),
+            timestamp: None,
+        });
+    }
+    issues
+}
+
+/// Loaded modules whose taint flags are set (O = out-of-tree, P = proprietary,
+/// E = unsigned, ...).
+#[cfg(target_os = "linux")]
+fn tainted_modules() -> Vec<DriverIssue> {
+    let Ok(entries) = std::fs::read_dir("/sys/module") else {
+        return Vec::new();
+    };
+    let mut issues: Vec<DriverIssue> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let taint = read_sysfs_string(&entry.path(), "taint").filter(|t| !t.is_empty())?;
+            let module = entry.file_name().to_string_lossy().to_string();
+            let version = read_sysfs_string(&entry.path(), "version");
+            Some(DriverIssue {
+                kind: "tainted_module".to_string(),
+                device: format!("Module {module}"),
+                detail: match version {
+                    Some(v) => format!("Taint {taint}, version {v}"),
+                    None => format!("Taint {taint}"),
+                },
+                timestamp: None,
+            })
+        })
+        .collect();
+    issues.sort_by(|a, b| a.device.cmp(&b.device));
+    issues
+}
+
+#[cfg(target_os = "linux")]
+fn parse_firmware_failures(log: &str) -> Vec<DriverIssue> {
+    let Ok(failed) = regex::Regex::new(
+        r"(?:Direct firmware load for (\S+) failed|firmware: failed to load (\S+)|failed to load firmware:? ?(\S+)?)",
+    ) else {
+        return Vec::new();
+    };
+    let mut seen = std::collections::HashSet::new();
+    let mut issues = Vec::new();
+    for line in log.lines() {
+        let Some(caps) = failed.captures(line) else {
+            continue;
+        };
+        let file = (1..=3)
+            .find_map(|i| caps.get(i))
+            .map(|m| m.as_str().trim_end_matches([',', '.']).to_string())
+            .unwrap_or_else(|| "unknown firmware".to_string());
+        // Drivers retry several paths; one entry per firmware file is enough.
+        if !seen.insert(file.clone()) {
+            continue;
+        }
+        let timestamp = line
+            .split_whitespace()
+            .next()
+            .filter(|t| t.len() >= 19 && t.as_bytes()[4] == b'-')
+            .map(|t| t.to_string());
+        issues.push(DriverIssue {
+            kind: "firmware_load_failure".to_string(),
+            device: file,
+            detail: line.trim().to_string(),
+            timestamp,
+        });
+    }
+    issues
+}
+
+#[cfg(target_os = "linux")]
+fn parse_fwupd_updates(json: &str) -> Vec<DriverIssue> {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(json) else {
+        return Vec::new();
+    };
+    let devices = value
+        .get("Devices")
+        .and_then(|d| d.as_array())
+        .cloned()
+        .unwrap_or_default();
+    devices
+        .iter()
+        .filter_map(|device| {
+            let release = device.get("Releases")?.as_array()?.first()?;
+            let name = device
+                .get("Name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("Unknown device");
+            let current = device
+                .get("Version")
+                .and_then(|v| v.as_str())
+                .unwrap_or("?");
+            let available = release
+                .get("Version")
+                .and_then(|v| v.as_str())
+                .unwrap_or("?");
+            let timestamp = release
+                .get("Created")
+                .and_then(|v| v.as_i64())
+                .and_then(|secs| chrono::DateTime::<Utc>::from_timestamp(secs, 0))
+                .map(|t| t.to_rfc3339());
+            Some(DriverIssue {
+                kind: "firmware_update".to_string(),
+                device: name.to_string(),
+                detail: format!("{current} -> {available}"),
+                timestamp,
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn pending_firmware_updates() -> Vec<DriverIssue> {
+    // fwupdmgr exits non-zero when there is nothing to update or no daemon.
+    match Command::new("fwupdmgr")
+        .args(["get-updates", "--json"])
+        .output()
+    {
+        Ok(out) if out.status.success() => {
+            parse_fwupd_updates(&String::from_utf8_lossy(&out.stdout))
+        }
+        _ => Vec::new(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn get_driver_status_linux() -> DriverStatus {
+    let mut issues = pending_firmware_updates();
+    issues.extend(unbound_pci_devices());
+    issues.extend(unbound_usb_devices());
+    issues.extend(tainted_modules());
+    issues.extend(parse_firmware_failures(&read_kernel_log(&["-b"])));
+
+    let kernel_taint = std::fs::read_to_string("/proc/sys/kernel/tainted")
+        .ok()
+        .and_then(|s| s.trim().parse().ok());
+
+    DriverStatus {
+        outdated_count: issues.len(),
+        sample: issues.iter().take(5).map(DriverIssue::as_entry).collect(),
+        raw: None,
+        kernel_taint,
+        issues,
+    }
+}
+
+//
+// ───────── OS updates ─────────
+//
+
+// Uptime after which we suggest a reboot even without a pending-reboot flag.
+#[cfg(target_os = "linux")]
+const UPDATES_REBOOT_NUDGE_DAYS: u64 = 14;
+
+#[derive(Serialize, Default, Clone)]
+struct PendingUpdate {
+    name: String,
+    current_version: Option<String>,
+    new_version: Option<String>,
+    security: bool,
+}
+
+#[derive(Serialize, Default)]
+struct UpdateStatus {
+    // "apt", "dnf" or "zypper"; None when no supported manager was found.
+    package_manager: Option<String>,
+    pending_count: usize,
+    security_count: usize,
+    packages: Vec<PendingUpdate>,
+    last_checked: Option<String>,
+    last_installed: Option<String>,
+    reboot_required: bool,
+    reboot_required_packages: Vec<String>,
+    uptime_seconds: u64,
+    reboot_recommended: bool,
+    timestamp: String,
+}
+
+/// `apt list --upgradable`:
+/// `name/jammy-updates,jammy-security 1.2.4 amd64 [upgradable from: 1.2.3]`
+#[cfg(target_os = "linux")]
+fn parse_apt_upgradable(output: &str) -> Vec<PendingUpdate> {
+    output
+        .lines()
+        .filter(|line| line.contains("[upgradable from:"))
+        .filter_map(|line| {
+            let (name, rest) = line.split_once('/')?;
+            let mut parts = rest.split_whitespace();
+            let origins = parts.next().unwrap_or_default();
+            let new_version = parts.next().map(|v| v.to_string());
+            let current_version = line
+                .split_once("[upgradable from:")
+                .map(|(_, v)| v.trim().trim_end_matches(']').trim().to_string());
+            Some(PendingUpdate {
+                name: name.to_string(),
+                current_version,
+                new_version,
+                security: origins.split(',').any(|o| o.ends_with("-security")),
+            })
+        })
+        .collect()
+}
+
+/// `dnf check-update`: `name.arch  version  repo`, stopping at the
+/// "Obsoleting Packages" block.
+#[cfg(target_os = "linux")]
+fn parse_dnf_check_update(output: &str) -> Vec<PendingUpdate> {
+    output
+        .lines()
+        .take_while(|line| !line.starts_with("Obsoleting"))
+        .filter_map(|line| {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if parts.len() != 3 || !parts[0].contains('.') {
+                return None;
+            }
+            let name = parts[0]
+                .rsplit_once('.')
+                .map(|(n, _)| n)
+                .unwrap_or(parts[0]);
+            Some(PendingUpdate {
+                name: name.to_string(),
+                current_version: None,
+                new_version: Some(parts[1].to_string()),
+                security: false,
+            })
+        })
+        .collect()
+}
+
+/// `dnf updateinfo list --security`: `ADVISORY  Severity/Sec.  name-version.arch`.
+/// Returns the package NEVRAs so they can be matched against pending updates.
+#[cfg(target_os = "linux")]
+fn parse_dnf_security(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter(|line| line.contains("/Sec."))
+        .filter_map(|line| line.split_whitespace().nth(2).map(|s| s.to_string()))
+        .collect()
+}
+
+/// zypper tables (`list-updates`, `list-patches`) are `|`-separated with a header.
+#[cfg(target_os = "linux")]
+fn parse_zypper_table(output: &str) -> Vec<Vec<String>> {
+    output
+        .lines()
+        .filter(|line| line.contains('|') && !line.starts_with("--"))
+        .map(|line| {
+            line.split('|')
+                .map(|c| c.trim().to_string())
+                .collect::<Vec<_>>()
+        })
+        // Drop the header row.
+        .filter(|cols| {
+            !cols
+                .iter()
+                .any(|c| c == "Repository" || c == "Category" || c == "Name")
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn command_exists(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .map(|paths| std::env::split_paths(&paths).any(|dir| dir.join(program).is_file()))
+        .unwrap_or(false)
+}
+
+#[cfg(target_os = "linux")]
+fn command_stdout(program: &str, args: &[&str]) -> Option<(i32, String)> {
+    let output = Command::new(program).args(args).output().ok()?;
+    Some((
+        output.status.code().unwrap_or(-1),
+        String::from_utf8_lossy(&output.stdout).to_string(),
+    ))
+}
+
+/// Modification time of the newest of `paths` (files or directories).
+#[cfg(target_os = "linux")]
+fn newest_mtime(paths: &[&str]) -> Option<String> {
+    paths
+        .iter()
+        .filter_map(|p| std::fs::metadata(p).ok()?.modified().ok())
+        .max()
+        .map(|t| chrono::DateTime::<Utc>::from(t).to_rfc3339())
+}
+
+/// Install time of the most recently installed rpm (`rpm -qa --last`).
+#[cfg(target_os = "linux")]
+fn rpm_last_installed() -> Option<String> {
+    let (_, out) = command_stdout("rpm", &["-qa", "--last"])?;
+    let line = out.lines().next()?;
+    // "<nevra>   <date>": the date follows a run of spaces.
+    line.split_once("  ")
+        .map(|(_, date)| date.trim().to_string())
+}
+
+#[cfg(target_os = "linux")]
+fn dpkg_last_installed() -> Option<String> {
+    let log = std::fs::read_to_string("/var/log/dpkg.log").ok()?;
+    log.lines()
+        .rev()
+        .find(|line| line.contains(" upgrade ") || line.contains(" install "))
+        .map(|line| line.chars().take(19).collect())
+}
+
+#[cfg(target_os = "linux")]
+fn apt_updates(status: &mut UpdateStatus) {
+    status.package_manager = Some("apt".to_string());
+    if let Some((_, out)) = command_stdout("apt", &["list", "--upgradable"]) {
+        status.packages = parse_apt_upgradable(&out);
+    }
+    status.last_checked = newest_mtime(&[
+        "/var/lib/apt/periodic/update-success-stamp",
+        "/var/cache/apt/pkgcache.bin",
+        "/var/lib/apt/lists",
+    ]);
+    status.last_installed = dpkg_last_installed();
+    status.reboot_required = std::path::Path::new("/var/run/reboot-required").exists();
+    status.reboot_required_packages = std::fs::read_to_string("/var/run/reboot-required.pkgs")
+        .map(|s| {
+            s.lines()
+                .map(|l| l.trim().to_string())
+                .filter(|l| !l.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
+}
+
+#[cfg(target_os = "linux")]
+fn dnf_updates(status: &mut UpdateStatus) {
+    status.package_manager = Some("dnf".to_string());
+    // -C: use cached metadata so the check doesn't stall on slow mirrors.
+    if let Some((_, out)) = command_stdout("dnf", &["-q", "-C", "check-update"]) {
+        status.packages = parse_dnf_check_update(&out);
+    }
+    if let Some((_, out)) = command_stdout("dnf", &["-q", "-C", "updateinfo", "list", "--security"])
+    {
+        let advisories = parse_dnf_security(&out);
+        for pkg in status.packages.iter_mut() {
+            let prefix = format!("{}-", pkg.name);
+            pkg.security = advisories.iter().any(|nevra| {
+                nevra
+                    .strip_prefix(&prefix)
+                    .map(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+                    .unwrap_or(false)
+            });
+        }
+    }
+    status.last_checked = newest_mtime(&["/var/cache/dnf", "/var/cache/libdnf5"]);
+    status.last_installed = rpm_last_installed();
+    // needs-restarting -r exits 1 when a reboot is needed.
+    status.reboot_required = command_stdout("needs-restarting", &["-r"])
+        .map(|(code, _)| code == 1)
+        .unwrap_or(false);
+}
+
+#[cfg(target_os = "linux")]
+fn zypper_updates(status: &mut UpdateStatus) {
+    status.package_manager = Some("zypper".to_string());
+    if let Some((_, out)) = command_stdout("zypper", &["-q", "--no-refresh", "list-updates"]) {
+        // S | Repository | Name | Current Version | Available Version | Arch
+        status.packages = parse_zypper_table(&out)
+            .into_iter()
+            .filter(|cols| cols.len() >= 5)
+            .map(|cols| PendingUpdate {
+                name: cols[2].clone(),
+                current_version: Some(cols[3].clone()),
+                new_version: Some(cols[4].clone()),
+                security: false,
+            })
+            .collect();
+    }
+    if let Some((_, out)) = command_stdout(
+        "zypper",
+        &[
+            "-q",
+            "--no-refresh",
+            "list-patches",
+            "--category",
+            "security",
+        ],
+    ) {
+        // Patches aren't mapped to packages; count them on their own.
+        status.security_count = parse_zypper_table(&out).len();
+    }
+    status.last_checked = newest_mtime(&["/var/cache/zypp/raw"]);
+    status.last_installed = rpm_last_installed();
+    // zypper needs-rebooting exits 102 when a reboot is needed.
+    status.reboot_required = std::path::Path::new("/run/reboot-needed").exists()
+        || command_stdout("zypper", &["needs-rebooting"])
+            .map(|(code, _)| code == 102)
+            .unwrap_or(false);
+}
+
+#[tauri::command]
+async fn get_update_status() -> Result<UpdateStatus, String> {
+    #[cfg(target_os = "linux")]
+    {
+        tauri::async_runtime::spawn_blocking(|| {
+            let mut status = UpdateStatus::default();
+            if command_exists("apt") && command_exists("dpkg") {
+                apt_updates(&mut status);
+            } else if command_exists("dnf") {
+                dnf_updates(&mut status);
+            } else if command_exists("zypper") {
+                zypper_updates(&mut status);
+            }
+
+            status.pending_count = status.packages.len();
+            status.security_count = status
+                .security_count
+                .max(status.packages.iter().filter(|p| p.security).count());
+            status.uptime_seconds = System::new().uptime();
+            status.reboot_recommended = status.reboot_required
+                || status.uptime_seconds > UPDATES_REBOOT_NUDGE_DAYS * 86_400;
+            status.timestamp = Utc::now().to_rfc3339();
+            Ok(status)
+        })
+        .await
+        .map_err(|e| format!("Thread join error: {e}"))?
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("Update status is only supported on Linux.".to_string())
+    }
+}
+
+//
+// ───────── Startup items ─────────
+//
+
+// Disabled entries and what they looked like before, so a change can be undone.
+const STARTUP_JOURNAL_FILE: &str = "startup-changes.json";
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+struct StartupItem {
+    // "xdg:<file>.desktop", "systemd-user:<unit>", "run:<hive>:<Run|Run32>:<name>"
+    // or "folder:<hive>:<file>".
+    id: String,
+    name: String,
+    command: String,
+    // "xdg-autostart", "systemd-user", "registry-run" or "startup-folder".
+    source: String,
+    // "user" or "system".
+    scope: String,
+    enabled: bool,
+    publisher: Option<String>,
+    location: Option<String>,
+    undoable: bool,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+struct StartupChange {
+    item_id: String,
+    name: String,
+    // Backend-specific prior state: file contents for XDG entries, the
+    // StartupApproved bytes on Windows. None means "did not exist".
+    previous: Option<String>,
+    timestamp: String,
+}
+
+fn startup_source_for_id(id: &str) -> &'static str {
+    match id.split(':').next().unwrap_or_default() {
+        "xdg" => "xdg-autostart",
+        "systemd-user" => "systemd-user",
+        "run" => "registry-run",
+        "folder" => "startup-folder",
+        _ => "unknown",
+    }
+}
+
+fn startup_journal_path(app: &AppHandle) -> Option<std::path::PathBuf> {
+    app.path()
+        .app_local_data_dir()
+        .ok()
+        .map(|dir| dir.join(STARTUP_JOURNAL_FILE))
+}
+
+fn read_startup_journal(app: &AppHandle) -> Vec<StartupChange> {
+    startup_journal_path(app)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .unwrap_or_default()
+}
+
+fn write_startup_journal(app: &AppHandle, changes: &[StartupChange]) -> Result<(), String> {
+    let path = startup_journal_path(app).ok_or_else(|| "No app data dir".to_string())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(changes).map_err(|e| e.to_string())?;
+    std::fs::write(&path, json).map_err(|e| e.to_string())
+}
+
+/// Key/value pairs of the `[Desktop Entry]` group, without localized keys.
+#[cfg(target_os = "linux")]
+fn parse_desktop_entry(text: &str) -> std::collections::HashMap<String, String> {
+    let mut in_entry = false;
+    let mut fields = std::collections::HashMap::new();
+    for line in text.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_entry = line == "[Desktop Entry]";
+            continue;
+        }
+        if !in_entry || line.starts_with('#') {
+            continue;
+        }
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim();
+            if !key.contains('[') {
+                fields.insert(key.to_string(), value.trim().to_string());
+            }
+        }
+    }
+    fields
+}
+
+/// Sets `key=value` in the `[Desktop Entry]` group, replacing an existing line.
+#[cfg(target_os = "linux")]
+fn set_desktop_key(text: &str, key: &str, value: &str) -> String {
+    let mut out: Vec<String> = Vec::new();
+    let mut in_entry = false;
+    let mut written = false;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') {
+            if in_entry && !written {
+                out.push(format!("{key}={value}"));
+                written = true;
+            }
+            in_entry = trimmed == "[Desktop Entry]";
+        } else if in_entry
+            && trimmed
+                .split_once('=')
+                .map(|(k, _)| k.trim() == key)
+                .unwrap_or(false)
+        {
+            if !written {
+                out.push(format!("{key}={value}"));
+                written = true;
+            }
+            continue;
+        }
+        out.push(line.to_string());
+    }
+    if !written {
+        if !in_entry {
+            out.push("[Desktop Entry]".to_string());
+        }
+        out.push(format!("{key}={value}"));
+    }
+    out.join("\n") + "\n"
+}
+
+#[cfg(target_os = "linux")]
+fn desktop_entry_enabled(fields: &std::collections::HashMap<String, String>) -> bool {
+    let is = |key: &str, value: &str| {
+        fields
+            .get(key)
+            .map(|v| v.eq_ignore_ascii_case(value))
+            .unwrap_or(false)
+    };
+    !is("Hidden", "true") && !is("X-GNOME-Autostart-enabled", "false")
+}
+
+#[cfg(target_os = "linux")]
+fn user_autostart_dir() -> Option<std::path::PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|v| !v.is_empty())
+        .map(std::path::PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|h| std::path::PathBuf::from(h).join(".config")))
+        .map(|dir| dir.join("autostart"))
+}
+
+#[cfg(target_os = "linux")]
+fn system_autostart_dirs() -> Vec<std::path::PathBuf> {
+    std::env::var("XDG_CONFIG_DIRS")
+        .ok()
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "/etc/xdg".to_string())
+        .split(':')
+        .map(|dir| std::path::Path::new(dir).join("autostart"))
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn xdg_startup_items() -> Vec<StartupItem> {
+    // A user file with the same name overrides the system one.
+    let mut files: std::collections::BTreeMap<String, (std::path::PathBuf, &str)> =
+        std::collections::BTreeMap::new();
+    for dir in system_autostart_dirs().into_iter().rev() {
+        for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+            files.insert(
+                entry.file_name().to_string_lossy().to_string(),
+                (entry.path(), "system"),
+            );
+        }
+    }
+    if let Some(dir) = user_autostart_dir() {
+        for entry in std::fs::read_dir(dir).into_iter().flatten().flatten() {
+            files.insert(
+                entry.file_name().to_string_lossy().to_string(),
+                (entry.path(), "user"),
+            );
+        }
+    }
+
+    files
+        .into_iter()
+        .filter(|(file, _)| file.ends_with(".desktop"))
+        .filter_map(|(file, (path, scope))| {
+            let fields = parse_desktop_entry(&std::fs::read_to_string(&path).ok()?);
+            // Drop desktop-file field codes (%u, %F, ...) from the command line.
+            let command = fields
+                .get("Exec")
+                .map(|exec| {
+                    exec.split_whitespace()
+                        .filter(|arg| !(arg.len() == 2 && arg.starts_with('%')))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .unwrap_or_default();
+            Some(StartupItem {
+                id: format!("xdg:{file}"),
+                name: fields
+                    .get("Name")
+                    .cloned()
+                    .unwrap_or_else(|| file.trim_end_matches(".desktop").to_string()),
+                command,
+                source: "xdg-autostart".to_string(),
+                scope: scope.to_string(),
+                enabled: desktop_entry_enabled(&fields),
+                publisher: None,
+                location: Some(path.to_string_lossy().to_string()),
+                undoable: false,
+            })
+        })
+        .collect()
+}
+
+/// `argv[]=` of a `systemctl show -p ExecStart` value.
+#[cfg(target_os = "linux")]
+fn parse_exec_start(value: &str) -> String {
+    value
+        .split(" ; ")
+        .find_map(|part| part.trim().strip_prefix("argv[]="))
+        .unwrap_or(value)
+        .trim()
+        .to_string()
+}
+
+#[cfg(target_os = "linux")]
+fn systemd_user_startup_items() -> Vec<StartupItem> {
+    let Some((_, listing)) = command_stdout(
+        "systemctl",
+        &[
+            "--user",
+            "list-unit-files",
+            "--type=service",
+            "--state=enabled",
+            "--no-legend",
+            "--plain",
+        ],
+    ) else {
+        return Vec::new();
+    };
+    let units: Vec<&str> = listing
+        .lines()
+        .filter_map(|line| line.split_whitespace().next())
+        // Templates only start through an instance.
+        .filter(|unit| !unit.ends_with("@.service"))
+        .collect();
+    if units.is_empty() {
+        return Vec::new();
+    }
+
+    let mut args = vec![
+        "--user",
+        "show",
+        "-p",
+        "Id",
+        "-p",
+        "Description",
+        "-p",
+        "ExecStart",
+        "-p",
+        "FragmentPath",
+    ];
+    args.extend(units.iter().copied());
+    let Some((_, details)) = command_stdout("systemctl", &args) else {
+        return Vec::new();
+    };
+    let home = std::env::var("HOME").unwrap_or_default();
+
+    // One blank-line separated block per unit.
+    details
+        .split("\n\n")
+        .filter_map(|block| {
+            let props: std::collections::HashMap<&str, &str> =
+                block.lines().filter_map(|l| l.split_once('=')).collect();
+            let unit = props.get("Id")?.to_string();
+            let fragment = props.get("FragmentPath").copied().unwrap_or_default();
+            Some(StartupItem {
+                id: format!("systemd-user:{unit}"),
+                name: props
+                    .get("Description")
+                    .filter(|d| !d.is_empty())
+                    .map(|d| d.to_string())
+                    .unwrap_or_else(|| unit.clone()),
+                command: props
+                    .get("ExecStart")
+                    .map(|v| parse_exec_start(v))
+                    .unwrap_or_default(),
+                source: "systemd-user".to_string(),
+                scope: if !home.is_empty() && fragment.starts_with(&home) {
+                    "user".to_string()
+                } else {
+                    "system".to_string()
+                },
+                enabled: true,
+                publisher: None,
+                location: (!fragment.is_empty()).then(|| fragment.to_string()),
+                undoable: false,
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn list_startup_items_impl() -> Result<Vec<StartupItem>, String> {
+    let mut items = xdg_startup_items();
+    items.extend(systemd_user_startup_items());
+    Ok(items)
+}
+
+/// Display name of one startup item, looked up without listing them all.
+#[cfg(target_os = "linux")]
+fn startup_item_name_impl(id: &str) -> Option<String> {
+    if let Some(file) = id.strip_prefix("xdg:") {
+        if file.contains('/') {
+            return None;
+        }
+        // Same precedence as the listing: user entry first, then XDG_CONFIG_DIRS order.
+        let name = user_autostart_dir()
+            .into_iter()
+            .chain(system_autostart_dirs())
+            .find_map(|dir| std::fs::read_to_string(dir.join(file)).ok())
+            .and_then(|text| parse_desktop_entry(&text).remove("Name"));
+        return name.or_else(|| Some(file.trim_end_matches(".desktop").to_string()));
+    }
+
+    if let Some(unit) = id.strip_prefix("systemd-user:") {
+        return command_stdout(
+            "systemctl",
+            &["--user", "show", "-p", "Description", "--value", unit],
+        )
+        .map(|(_, out)| out.trim().to_string())
+        .filter(|description| !description.is_empty());
+    }
+
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn disable_startup_item_impl(id: &str) -> Result<Option<String>, String> {
+    if let Some(file) = id.strip_prefix("xdg:") {
+        if file.contains('/') || !file.ends_with(".desktop") {
+            return Err(format!("Invalid autostart entry: {file}"));
+        }
+        let user_dir = user_autostart_dir().ok_or_else(|| "No home directory".to_string())?;
+        let user_path = user_dir.join(file);
+        let previous = std::fs::read_to_string(&user_path).ok();
+        // System entries are overridden by a hidden copy in the user's autostart dir.
+        let base = match &previous {
+            Some(text) => text.clone(),
+            None => system_autostart_dirs()
+                .iter()
+                .find_map(|dir| std::fs::read_to_string(dir.join(file)).ok())
+                .ok_or_else(|| format!("Autostart entry {file} not found"))?,
+        };
+        std::fs::create_dir_all(&user_dir).map_err(|e| e.to_string())?;
+        std::fs::write(&user_path, set_desktop_key(&base, "Hidden", "true"))
+            .map_err(|e| format!("Failed to write {}: {e}", user_path.display()))?;
+        return Ok(previous);
+    }
+
+    if let Some(unit) = id.strip_prefix("systemd-user:") {
+        let output = Command::new("systemctl")
+            .args(["--user", "disable", unit])
+            .output()
+            .map_err(|e| format!("Failed to run systemctl: {e}"))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        // Units enabled globally (/etc/systemd/user) can't be disabled per user.
+        let still_enabled = command_stdout("systemctl", &["--user", "is-enabled", unit])
+            .map(|(_, out)| out.trim() == "enabled")
+            .unwrap_or(false);
+        if still_enabled {
+            return Err(format!(
+                "{unit} is enabled for all users and can't be disabled here"
+            ));
+        }
+        return Ok(None);
+    }
+
+    Err(format!("Unknown startup item: {id}"))
+}
+
+#[cfg(target_os = "linux")]
+fn restore_startup_item_impl(change: &StartupChange) -> Result<(), String> {
+    if let Some(file) = change.item_id.strip_prefix("xdg:") {
+        let user_path = user_autostart_dir()
+            .ok_or_else(|| "No home directory".to_string())?
+            .join(file);
+        return match &change.previous {
+            Some(text) => std::fs::write(&user_path, text).map_err(|e| e.to_string()),
+            None => std::fs::remove_file(&user_path).map_err(|e| e.to_string()),
+        };
+    }
+
+    if let Some(unit) = change.item_id.strip_prefix("systemd-user:") {
+        let output = Command::new("systemctl")
+            .args(["--user", "enable", unit])
+            .output()
+            .map_err(|e| format!("Failed to run systemctl: {e}"))?;
+        if !output.status.success() {
+            return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+        }
+        return Ok(());
+    }
+
+    Err(format!("Unknown startup item: {}", change.item_id))
+}
+
+#[cfg(target_os = "windows")]
+const STARTUP_LIST_SCRIPT: &str = r#"
+$approvedRoot = 'Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved'
+function Get-Approved($hive, $kind, $name) {
+  $v = (Get-ItemProperty -Path "$($hive):\$approvedRoot\$kind" -Name $name -ErrorAction SilentlyContinue).$name
+  # Even first byte = enabled, odd = disabled (what Task Manager writes).
+  if ($v -and $v.Length -gt 0) { return (($v[0] -band 1) -eq 0) }
+  return $true
+}
+function Get-Publisher($cmd) {
+  $exe = $null
+  if ($cmd -match '^\s*"([^"]+)"') { $exe = $Matches[1] } elseif ($cmd -match '^\s*(\S+?\.exe)') { $exe = $Matches[1] }
+  if ($exe) { $exe = [Environment]::ExpandEnvironmentVariables($exe) }
+  if ($exe -and (Test-Path -LiteralPath $exe)) { return (Get-Item -LiteralPath $exe).VersionInfo.CompanyName }
+  return $null
+}
+$skip = @('PSPath','PSParentPath','PSChildName','PSDrive','PSProvider')
+$items = @()
+$runKeys = @(
+  @{ Hive = 'HKCU'; Kind = 'Run'; Path = 'Software\Microsoft\Windows\CurrentVersion\Run' },
+  @{ Hive = 'HKLM'; Kind = 'Run'; Path = 'Software\Microsoft\Windows\CurrentVersion\Run' },
+  @{ Hive = 'HKLM'; Kind = 'Run32'; Path = 'Software\WOW6432Node\Microsoft\Windows\CurrentVersion\Run' }
+)
+foreach ($k in $runKeys) {
+  $props = Get-ItemProperty -Path "$($k.Hive):\$($k.Path)" -ErrorAction SilentlyContinue
+  if (-not $props) { continue }
+  foreach ($p in $props.PSObject.Properties) {
+    if ($skip -contains $p.Name) { continue }
+    $items += [pscustomobject]@{
+      id = "run:$($k.Hive):$($k.Kind):$($p.Name)"
+      name = $p.Name
+      command = [string]$p.Value
+      source = 'registry-run'
+      scope = $(if ($k.Hive -eq 'HKCU') { 'user' } else { 'system' })
+      enabled = (Get-Approved $k.Hive $k.Kind $p.Name)
+      publisher = (Get-Publisher ([string]$p.Value))
+      location = "$($k.Hive):\$($k.Path)"
+    }
+  }
+}
+$folders = @(
+  @{ Hive = 'HKCU'; Scope = 'user'; Path = [Environment]::GetFolderPath('Startup') },
+  @{ Hive = 'HKLM'; Scope = 'system'; Path = [Environment]::GetFolderPath('CommonStartup') }
+)
+$shell = New-Object -ComObject WScript.Shell
+foreach ($f in $folders) {
+  Get-ChildItem -LiteralPath $f.Path -File -ErrorAction SilentlyContinue | Where-Object { $_.Name -ne 'desktop.ini' } | ForEach-Object {
+    $target = $_.FullName
+    if ($_.Extension -eq '.lnk') { $target = $shell.CreateShortcut($_.FullName).TargetPath }
+    $items += [pscustomobject]@{
+      id = "folder:$($f.Hive):$($_.Name)"
+      name = $_.BaseName
+      command = $target
+      source = 'startup-folder'
+      scope = $f.Scope
+      enabled = (Get-Approved $f.Hive 'StartupFolder' $_.Name)
+      publisher = (Get-Publisher "`"$target`"")
+      location = $_.FullName
+    }
+  }
+}
+ConvertTo-Json -InputObject @($items) -Depth 3 -Compress
+"#;
+
+// Disables the way Task Manager does: 03 + FILETIME in StartupApproved.
+// Prints the previous value as hex so it can be restored.
+#[cfg(target_os = "windows")]
+const STARTUP_DISABLE_SCRIPT: &str = r#"
+$path = '__HIVE__:\Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\__KIND__'
+$name = '__NAME__'
+$old = (Get-ItemProperty -Path $path -Name $name -ErrorAction SilentlyContinue).$name
+if (-not (Test-Path $path)) { New-Item -Path $path -Force | Out-Null }
+$bytes = [byte[]](@(3, 0, 0, 0) + [BitConverter]::GetBytes([DateTime]::Now.ToFileTime()))
+Set-ItemProperty -Path $path -Name $name -Value $bytes -Type Binary -ErrorAction Stop
+if ($old) { [BitConverter]::ToString($old) } else { '' }
+"#;
+
+#[cfg(target_os = "windows")]
+const STARTUP_RESTORE_SCRIPT: &str = r#"
+$path = '__HIVE__:\Software\Microsoft\Windows\CurrentVersion\Explorer\StartupApproved\__KIND__'
+$name = '__NAME__'
+$old = '__OLD__'
+if ($old) {
+  $bytes = [byte[]]($old.Split('-') | ForEach-Object { [Convert]::ToByte($_, 16) })
+  Set-ItemProperty -Path $path -Name $name -Value $bytes -Type Binary -ErrorAction Stop
+} else {
+  Remove-ItemProperty -Path $path -Name $name -ErrorAction Stop
+}
+"#;
+
+/// Registry hive, StartupApproved subkey and value name for a Windows startup id.
+#[cfg(target_os = "windows")]
+fn startup_approved_target(id: &str) -> Result<(String, String, String), String> {
+    let unknown = || format!("Unknown startup item: {id}");
+    let (hive, kind, name) = if let Some(rest) = id.strip_prefix("run:") {
+        let mut parts = rest.splitn(3, ':');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(hive), Some(kind @ ("Run" | "Run32")), Some(name)) => (hive, kind, name),
+            _ => return Err(unknown()),
+        }
+    } else if let Some(rest) = id.strip_prefix("folder:") {
+        let (hive, name) = rest.split_once(':').ok_or_else(unknown)?;
+        (hive, "StartupFolder", name)
+    } else {
+        return Err(unknown());
+    };
+    if hive != "HKCU" && hive != "HKLM" {
+        return Err(unknown());
+    }
+    Ok((hive.to_string(), kind.to_string(), name.replace('\'', "''")))
+}
+
+#[cfg(target_os = "windows")]
+fn list_startup_items_impl() -> Result<Vec<StartupItem>, String> {
+    let output = powershell_output(STARTUP_LIST_SCRIPT)?;
+    if output.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    serde_json::from_str(&output).map_err(|e| format!("Parse startup items failed: {e}"))
+}
+
+/// Display name of one startup item, taken from its id like the listing script does.
+#[cfg(target_os = "windows")]
+fn startup_item_name_impl(id: &str) -> Option<String> {
+    if let Some(rest) = id.strip_prefix("run:") {
+        return rest.splitn(3, ':').nth(2).map(str::to_string);
+    }
+    let (_, file) = id.strip_prefix("folder:")?.split_once(':')?;
+    std::path::Path::new(file)
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn disable_startup_item_impl(id: &str) -> Result<Option<String>, String> {
+    let (hive, kind, name) = startup_approved_target(id)?;
+    let script = STARTUP_DISABLE_SCRIPT
+        .replace("__HIVE__", &hive)
+        .replace("__KIND__", &kind)
+        .replace("__NAME__", &name);
+    let previous = powershell_output(&script)?;
+    Ok((!previous.trim().is_empty()).then(|| previous.trim().to_string()))
+}
+
+#[cfg(target_os = "windows")]
+fn restore_startup_item_impl(change: &StartupChange) -> Result<(), String> {
+    let (hive, kind, name) = startup_approved_target(&change.item_id)?;
+    let old = change.previous.clone().unwrap_or_default();
+    if !old.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+        return Err("Corrupt startup journal entry".to_string());
+    }
+    let script = STARTUP_RESTORE_SCRIPT
+        .replace("__HIVE__", &hive)
+        .replace("__KIND__", &kind)
+        .replace("__NAME__", &name)
+        .replace("__OLD__", &old);
+    powershell_output(&script).map(|_| ())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn list_startup_items_impl() -> Result<Vec<StartupItem>, String> {
+    Err("Startup items are only supported on Windows and Linux.".to_string())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn startup_item_name_impl(_id: &str) -> Option<String> {
+    None
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn disable_startup_item_impl(_id: &str) -> Result<Option<String>, String> {
+    Err("Startup items are only supported on Windows and Linux.".to_string())
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn restore_startup_item_impl(_change: &StartupChange) -> Result<(), String> {
+    Err("Startup items are only supported on Windows and Linux.".to_string())
+}
+
+#[tauri::command]
+async fn get_startup_items(app: AppHandle) -> Result<Vec<StartupItem>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut items = list_startup_items_impl()?;
+        let journal = read_startup_journal(&app);
+        for item in items.iter_mut() {
+            item.undoable = journal.iter().any(|c| c.item_id == item.id);
+        }
+        // Disabled systemd units drop out of the listing; keep them visible for undo.
+        for change in &journal {
+            if !items.iter().any(|item| item.id == change.item_id) {
+                items.push(StartupItem {
+                    id: change.item_id.clone(),
+                    name: change.name.clone(),
+                    source: startup_source_for_id(&change.item_id).to_string(),
+                    enabled: false,
+                    undoable: true,
+                    ..Default::default()
+                });
+            }
+        }
+        Ok(items)
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+#[tauri::command]
+async fn disable_startup_item(app: AppHandle, id: String) -> Result<StartupChange, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let name = startup_item_name_impl(&id).unwrap_or_else(|| id.clone());
+        let previous = disable_startup_item_impl(&id)?;
+
+        let change = StartupChange {
+            item_id: id,
+            name,
+            previous,
+            timestamp: Utc::now().to_rfc3339(),
+        };
+        let mut journal = read_startup_journal(&app);
+        // Keep the oldest record: it holds the state from before we touched the entry.
+        if !journal.iter().any(|c| c.item_id == change.item_id) {
+            journal.push(change.clone());
+            write_startup_journal(&app, &journal)?;
+        }
+        Ok(change)
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+#[tauri::command]
+async fn undo_startup_change(app: AppHandle, id: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let mut journal = read_startup_journal(&app);
+        let index = journal
+            .iter()
+            .position(|c| c.item_id == id)
+            .ok_or_else(|| format!("No recorded change for {id}"))?;
+        restore_startup_item_impl(&journal[index])?;
+        journal.remove(index);
+        write_startup_journal(&app, &journal)
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+//
+// ───────── Service health ─────────
+//
+
+// Comma-separated unit names that may be restarted from the app.
+const SERVICE_ALLOWLIST_ENV: &str = "GOLPAC_SERVICE_RESTART_ALLOWLIST";
+const SERVICE_DEFAULT_ALLOWLIST: [&str; 8] = [
+    "cups.service",
+    "cups-browsed.service",
+    "NetworkManager.service",
+    "sssd.service",
+    "bluetooth.service",
+    "systemd-resolved.service",
+    "pipewire.service",
+    "wireplumber.service",
+];
+#[cfg(target_os = "linux")]
+const SERVICE_LOG_LINES: &str = "5";
+
+#[derive(Serialize, Default, Clone)]
+struct UnitHealth {
+    unit: String,
+    // "system" or "user".
+    scope: String,
+    description: String,
+    load_state: String,
+    active_state: String,
+    sub_state: String,
+    restart_allowed: bool,
+    last_log: Vec<String>,
+}
+
+#[derive(Serialize, Default)]
+struct ServiceHealth {
+    // `systemctl is-system-running`: "running", "degraded", ...
+    system_state: Option<String>,
+    user_state: Option<String>,
+    units: Vec<UnitHealth>,
+    restart_allowlist: Vec<String>,
+    timestamp: String,
+}
+
+fn normalize_unit_name(unit: &str) -> String {
+    let unit = unit.trim();
+    if unit.contains('.') {
+        unit.to_string()
+    } else {
+        format!("{unit}.service")
+    }
+}
+
+fn service_restart_allowlist() -> Vec<String> {
+    match std::env::var(SERVICE_ALLOWLIST_ENV) {
+        Ok(list) if !list.trim().is_empty() => list
+            .split(',')
+            .filter(|u| !u.trim().is_empty())
+            .map(normalize_unit_name)
+            .collect(),
+        _ => SERVICE_DEFAULT_ALLOWLIST
+            .iter()
+            .map(|u| u.to_string())
+            .collect(),
+    }
+}
+
+/// `systemctl list-units --plain --no-legend` rows: UNIT LOAD ACTIVE SUB DESCRIPTION.
+#[cfg(target_os = "linux")]
+fn parse_unit_list(output: &str, scope: &str) -> Vec<UnitHealth> {
+    output
+        .lines()
+        .filter_map(|line| {
+            // Failed rows may still carry a status bullet.
+            let line = line.trim().trim_start_matches(['●', '*']).trim();
+            let mut cols = line.split_whitespace();
+            let unit = cols.next()?.to_string();
+            let load_state = cols.next()?.to_string();
+            let active_state = cols.next()?.to_string();
+            let sub_state = cols.next()?.to_string();
+            Some(UnitHealth {
+                unit,
+                scope: scope.to_string(),
+                description: cols.collect::<Vec<_>>().join(" "),
+                load_state,
+                active_state,
+                sub_state,
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn systemctl_args(scope: &str) -> Vec<&'static str> {
+    if scope == "user" {
+        vec!["--user"]
+    } else {
+        vec!["--system"]
+    }
+}
+
+/// Failed units plus units stuck in a restart loop.
+#[cfg(target_os = "linux")]
+fn unhealthy_units(scope: &str) -> Vec<UnitHealth> {
+    let mut args = systemctl_args(scope);
+    args.extend([
+        "list-units",
+        "--all",
+        "--plain",
+        "--no-legend",
+        "--no-pager",
+        "--state=failed,auto-restart",
+    ]);
+    command_stdout("systemctl", &args)
+        .map(|(_, out)| parse_unit_list(&out, scope))
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "linux")]
+fn unit_last_log(unit: &str, scope: &str) -> Vec<String> {
+    let selector = if scope == "user" {
+        "--user-unit"
+    } else {
+        "--unit"
+    };
+    command_stdout(
+        "journalctl",
+        &[
+            selector,
+            unit,
+            "-n",
+            SERVICE_LOG_LINES,
+            "--no-pager",
+            "-o",
+            "short-iso",
+        ],
+    )
+    .map(|(_, out)| {
+        out.lines()
+            .filter(|l| !l.starts_with("-- "))
+            .map(|l| l.to_string())
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(target_os = "linux")]
+fn systemd_state(scope: &str) -> Option<String> {
+    let mut args = systemctl_args(scope);
+    args.push("is-system-running");
+    command_stdout("systemctl", &args)
+        .map(|(_, out)| out.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Restarts through systemd's D-Bus API so polkit decides (and may prompt)
+/// instead of needing sudo.
+#[cfg(target_os = "linux")]
+fn restart_unit_dbus(unit: &str, scope: &str) -> Result<String, String> {
+    let conn = if scope == "user" {
+        zbus::blocking::Connection::session()
+    } else {
+        zbus::blocking::Connection::system()
+    }
+    .map_err(|e| format!("Failed to connect to D-Bus: {e}"))?;
+    let proxy = zbus::blocking::Proxy::new(
+        &conn,
+        "org.freedesktop.systemd1",
+        "/org/freedesktop/systemd1",
+        "org.freedesktop.systemd1.Manager",
+    )
+    .map_err(|e| format!("Failed to reach systemd: {e}"))?;
+    let job: Option<zbus::zvariant::OwnedObjectPath> = proxy
+        .call_with_flags(
+            "RestartUnit",
+            zbus::proxy::MethodFlags::AllowInteractiveAuth.into(),
+            &(unit, "replace"),
+        )
+        .map_err(|e| format!("Failed to restart {unit}: {e}"))?;
+    Ok(job.map(|j| j.as_str().to_string()).unwrap_or_default())
+}
+
+#[tauri::command]
+async fn get_service_health() -> Result<ServiceHealth, String> {
+    #[cfg(target_os = "linux")]
+    {
+        tauri::async_runtime::spawn_blocking(|| {
+            let allowlist = service_restart_allowlist();
+            let mut units = unhealthy_units("system");
+            units.extend(unhealthy_units("user"));
+            for unit in units.iter_mut() {
+                unit.restart_allowed = allowlist.contains(&unit.unit);
+                unit.last_log = unit_last_log(&unit.unit, &unit.scope);
+            }
+            Ok(ServiceHealth {
+                system_state: systemd_state("system"),
+                user_state: systemd_state("user"),
+                units,
+                restart_allowlist: allowlist,
+                timestamp: Utc::now().to_rfc3339(),
+            })
+        })
+        .await
+        .map_err(|e| format!("Thread join error: {e}"))?
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("Service health is only supported on Linux.".to_string())
+    }
+}
+
+#[tauri::command]
+async fn restart_service(unit: String, scope: Option<String>) -> Result<String, String> {
+    let unit = normalize_unit_name(&unit);
+    if !service_restart_allowlist().contains(&unit) {
+        return Err(format!("{unit} is not in the restart allowlist"));
+    }
+    let scope = scope.unwrap_or_else(|| "system".to_string());
+
+    #[cfg(target_os = "linux")]
+    {
+        tauri::async_runtime::spawn_blocking(move || restart_unit_dbus(&unit, &scope))
+            .await
+            .map_err(|e| format!("Thread join error: {e}"))?
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = scope;
+        Err("Service restart is only supported on Linux.".to_string())
+    }
+}
+
+//
+// ───────── System logs & crashes ─────────
+//
+
+const LOG_DEFAULT_HOURS: u64 = 24;
+const LOG_MAX_HOURS: u64 = 24 * 14;
+// journalctl output is capped so a noisy box can't stall the collector.
+#[cfg(target_os = "linux")]
+const LOG_MAX_ENTRIES: &str = "5000";
+#[cfg(target_os = "linux")]
+const LOG_MAX_GROUPS: usize = 30;
+
+#[derive(Serialize, Default, Clone)]
+struct LogGroup {
+    // Unit or syslog identifier the messages came from.
+    source: String,
+    priority: u8,
+    // First message of the group, with usernames redacted.
+    message: String,
+    count: usize,
+    first_seen: Option<String>,
+    last_seen: Option<String>,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct CoredumpEntry {
+    timestamp: Option<String>,
+    pid: Option<u32>,
+    executable: String,
+    signal: Option<String>,
+    core_available: bool,
+}
+
+#[derive(Serialize, Default)]
+struct LogSummary {
+    window_hours: u64,
+    error_count: usize,
+    errors: Vec<LogGroup>,
+    kernel_warning_count: usize,
+    kernel_warnings: Vec<LogGroup>,
+    oom_events: Vec<OomEvent>,
+    coredumps: Vec<CoredumpEntry>,
+    timestamp: String,
+}
+
+#[cfg(target_os = "linux")]
+struct JournalEntry {
+    source: String,
+    priority: u8,
+    message: String,
+    timestamp: Option<String>,
+}
+
+/// `journalctl -o json` lines. MESSAGE is a byte array when it isn't UTF-8.
+#[cfg(target_os = "linux")]
+fn parse_journal_entries(output: &str) -> Vec<JournalEntry> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|v| {
+            let message = match v.get("MESSAGE")? {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Array(bytes) => String::from_utf8_lossy(
+                    &bytes
+                        .iter()
+                        .filter_map(|b| b.as_u64().map(|b| b as u8))
+                        .collect::<Vec<u8>>(),
+                )
+                .to_string(),
+                _ => return None,
+            };
+            let field = |key: &str| v.get(key).and_then(|x| x.as_str());
+            let source = field("_SYSTEMD_UNIT")
+                .or_else(|| field("SYSLOG_IDENTIFIER"))
+                .or_else(|| field("_COMM"))
+                .or_else(|| (field("_TRANSPORT") == Some("kernel")).then_some("kernel"))
+                .unwrap_or("unknown")
+                .to_string();
+            Some(JournalEntry {
+                source,
+                priority: field("PRIORITY").and_then(|p| p.parse().ok()).unwrap_or(3),
+                message,
+                timestamp: field("__REALTIME_TIMESTAMP")
+                    .and_then(|t| t.parse::<i64>().ok())
+                    .and_then(chrono::DateTime::<Utc>::from_timestamp_micros)
+                    .map(|t| t.to_rfc3339()),
+            })
+        })
+        .collect()
+}
+
+/// Replaces home paths and known account names with `<user>`.
+#[cfg(target_os = "linux")]
+fn redact_usernames(text: &str, names: &[String]) -> String {
+    static HOME_PATH: once_cell::sync::Lazy<Option<regex::Regex>> =
+        once_cell::sync::Lazy::new(|| regex::Regex::new(r"/(home|Users)/[^/\s:]+").ok());
+    let mut out = match HOME_PATH.as_ref() {
+        Some(re) => re.replace_all(text, "/$1/<user>").to_string(),
+        None => text.to_string(),
+    };
+    for name in names.iter().filter(|n| n.len() > 2) {
+        if let Ok(re) = regex::Regex::new(&format!(r"\b{}\b", regex::escape(name))) {
+            out = re.replace_all(&out, "<user>").to_string();
+        }
+    }
+    out
+}
+
+/// Collapses the parts that differ between repeats (numbers, hex ids, PIDs)
+/// so the same message groups together.
+#[cfg(target_os = "linux")]
+fn normalize_log_message(message: &str) -> String {
+    static VOLATILE: once_cell::sync::Lazy<Option<regex::Regex>> =
+        once_cell::sync::Lazy::new(|| {
+            regex::Regex::new(r"0x[0-9a-fA-F]+|[0-9a-fA-F]{8}-[0-9a-fA-F-]{27}|\d+").ok()
+        });
+    match VOLATILE.as_ref() {
+        Some(re) => re.replace_all(message, "#").to_string(),
+        None => message.to_string(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn group_log_entries(entries: &[JournalEntry], names: &[String]) -> Vec<LogGroup> {
+    let mut groups: Vec<LogGroup> = Vec::new();
+    let mut index: std::collections::HashMap<(String, String), usize> =
+        std::collections::HashMap::new();
+    for entry in entries {
+        let key = (entry.source.clone(), normalize_log_message(&entry.message));
+        match index.get(&key) {
+            Some(&i) => {
+                let group = &mut groups[i];
+                group.count += 1;
+                group.priority = group.priority.min(entry.priority);
+                group.last_seen = entry.timestamp.clone().or(group.last_seen.take());
+            }
+            None => {
+                index.insert(key, groups.len());
+                groups.push(LogGroup {
+                    source: entry.source.clone(),
+                    priority: entry.priority,
+                    message: redact_usernames(entry.message.trim(), names),
+                    count: 1,
+                    first_seen: entry.timestamp.clone(),
+                    last_seen: entry.timestamp.clone(),
+                });
+            }
+        }
+    }
+    groups.sort_by(|a, b| b.count.cmp(&a.count).then(a.priority.cmp(&b.priority)));
+    groups.truncate(LOG_MAX_GROUPS);
+    groups
+}
+
+#[cfg(target_os = "linux")]
+fn signal_name(signal: u64) -> String {
+    match signal {
+        3 => "SIGQUIT".to_string(),
+        4 => "SIGILL".to_string(),
+        5 => "SIGTRAP".to_string(),
+        6 => "SIGABRT".to_string(),
+        7 => "SIGBUS".to_string(),
+        8 => "SIGFPE".to_string(),
+        11 => "SIGSEGV".to_string(),
+        other => format!("signal {other}"),
+    }
+}
+
+/// `coredumpctl list --json=short`.
+#[cfg(target_os = "linux")]
+fn parse_coredumps(json: &str, names: &[String]) -> Vec<CoredumpEntry> {
+    let Ok(serde_json::Value::Array(items)) = serde_json::from_str::<serde_json::Value>(json)
+    else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .map(|item| CoredumpEntry {
+            timestamp: item
+                .get("time")
+                .and_then(|t| t.as_i64())
+                .and_then(chrono::DateTime::<Utc>::from_timestamp_micros)
+                .map(|t| t.to_rfc3339()),
+            pid: item.get("pid").and_then(|p| p.as_u64()).map(|p| p as u32),
+            executable: redact_usernames(
+                item.get("exe")
+                    .and_then(|e| e.as_str())
+                    .unwrap_or("unknown"),
+                names,
+            ),
+            signal: item.get("sig").and_then(|s| s.as_u64()).map(signal_name),
+            core_available: item
+                .get("corefile")
+                .and_then(|c| c.as_str())
+                .map(|c| c == "present")
+                .unwrap_or(false),
+        })
+        .collect()
+}
+
+/// Local account names (UID >= 1000) plus the current user, for redaction.
+#[cfg(target_os = "linux")]
+fn local_usernames() -> Vec<String> {
+    let mut names: Vec<String> = std::fs::read_to_string("/etc/passwd")
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let cols: Vec<&str> = line.split(':').collect();
+            let uid: u32 = cols.get(2)?.parse().ok()?;
+            (1000..65534).contains(&uid).then(|| cols[0].to_string())
+        })
+        .collect();
+    names.push(whoami::username());
+    names.sort();
+    names.dedup();
+    names
+}
+
+#[cfg(target_os = "linux")]
+fn journal_json(args: &[&str]) -> Vec<JournalEntry> {
+    let mut all = vec!["--no-pager", "-o", "json", "-n", LOG_MAX_ENTRIES];
+    all.extend_from_slice(args);
+    command_stdout("journalctl", &all)
+        .map(|(_, out)| parse_journal_entries(&out))
+        .unwrap_or_default()
+}
+
+#[tauri::command]
+async fn collect_system_logs(hours: Option<u64>) -> Result<LogSummary, String> {
+    let hours = hours.unwrap_or(LOG_DEFAULT_HOURS).clamp(1, LOG_MAX_HOURS);
+
+    #[cfg(target_os = "linux")]
+    {
+        tauri::async_runtime::spawn_blocking(move || {
+            let since = format!("-{hours}h");
+            let names = local_usernames();
+
+            let errors = journal_json(&["-p", "err", "--since", &since]);
+            let kernel = journal_json(&["-k", "-p", "warning..warning", "--since", &since]);
+            let coredumps = command_stdout(
+                "coredumpctl",
+                &["list", "--json=short", "--no-pager", "--since", &since],
+            )
+            .map(|(_, out)| parse_coredumps(&out, &names))
+            .unwrap_or_default();
+            let mut oom_events = parse_oom_events(&read_kernel_log(&["--since", &since]));
+            for event in oom_events.iter_mut() {
+                event.message = redact_usernames(&event.message, &names);
+            }
+
+            Ok(LogSummary {
+                window_hours: hours,
+                error_count: errors.len(),
+                errors: group_log_entries(&errors, &names),
+                kernel_warning_count: kernel.len(),
+                kernel_warnings: group_log_entries(&kernel, &names),
+                oom_events,
+                coredumps,
+                timestamp: Utc::now().to_rfc3339(),
+            })
+        })
+        .await
+        .map_err(|e| format!("Thread join error: {e}"))?
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = hours;
+        Err("Log collection is only supported on Linux.".to_string())
+    }
+}
+
+//
+// ───────── Crash watcher ─────────
+//
+
+// Comma-separated process names to watch; defaults cover Sage, browsers and mail/office.
+const CRASH_WATCH_ENV: &str = "GOLPAC_CRASH_WATCH_APPS";
+const CRASH_WATCH_DEFAULT: [&str; 9] = [
+    "pvxwin32",
+    "chrome",
+    "msedge",
+    "brave",
+    "firefox",
+    "soffice.bin",
+    "thunderbird",
+    "evolution",
+    "outlook",
+];
+const CRASH_POLL_SECS: u64 = 30;
+const CRASH_MAX_HISTORY: usize = 20;
+const CRASH_EVENT: &str = "app-crash";
+
+#[derive(Serialize, Default, Clone)]
+struct CrashReport {
+    app: String,
+    pid: Option<u32>,
+    // "coredump", "segfault", "trap", "oom_kill", "application_error" or "application_hang".
+    kind: String,
+    signal: Option<String>,
+    detail: String,
+    timestamp: String,
+    foreground_window: Option<String>,
+    memory_used_gb: f64,
+    memory_total_gb: f64,
+    top_processes: Vec<ProcessMemory>,
+    recent_logs: Vec<String>,
+}
+
+static CRASH_HISTORY: once_cell::sync::Lazy<std::sync::Mutex<Vec<CrashReport>>> =
+    once_cell::sync::Lazy::new(|| std::sync::Mutex::new(Vec::new()));
+
+fn crash_watch_list() -> Vec<String> {
+    match std::env::var(CRASH_WATCH_ENV) {
+        Ok(list) if !list.trim().is_empty() => list
+            .split(',')
+            .map(|a| a.trim().to_lowercase())
+            .filter(|a| !a.is_empty())
+            .collect(),
+        _ => CRASH_WATCH_DEFAULT.iter().map(|a| a.to_string()).collect(),
+    }
+}
+
+/// Matches an executable path or process name against the watch list.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn crash_watched_app(exe: &str, watched: &[String]) -> Option<String> {
+    let base = exe.rsplit(['/', '\\']).next().unwrap_or(exe);
+    watched
+        .iter()
+        .find(|w| process_name_matches(base, w))
+        .map(|_| base.to_string())
+}
+
+/// Leading timestamp of a `journalctl -o short-iso` or `dmesg --time-format iso` line.
+#[cfg(target_os = "linux")]
+fn kernel_log_time(line: &str) -> Option<chrono::DateTime<Utc>> {
+    let token = line.split_whitespace().next()?.replace(',', ".");
+    chrono::DateTime::parse_from_str(&token, "%Y-%m-%dT%H:%M:%S%.f%z")
+        .ok()
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// Signal the kernel sends for a `traps: app[pid] trap <name> ip:...` line;
+/// unlisted traps are left unknown rather than guessed.
+#[cfg(target_os = "linux")]
+fn kernel_trap_signal(trap: &str) -> Option<&'static str> {
+    match trap {
+        "invalid opcode" => Some("SIGILL"),
+        "divide error" => Some("SIGFPE"),
+        "int3" => Some("SIGTRAP"),
+        "stack segment" | "alignment check" => Some("SIGBUS"),
+        _ => None,
+    }
+}
+
+/// Kernel crash lines: `chrome[1234]: segfault at ...`, `traps: app[1234] general
+/// protection ...` (both SIGSEGV) and `traps: app[1234] trap invalid opcode ...`.
+#[cfg(target_os = "linux")]
+fn parse_kernel_crashes(log: &str, watched: &[String]) -> Vec<CrashReport> {
+    let Ok(re) = regex::Regex::new(
+        r"(\S+)\[(\d+)\]:? (?:segfault at|general protection|trap (\S+(?: \S+)*?) ip)",
+    ) else {
+        return Vec::new();
+    };
+    log.lines()
+        .filter_map(|line| {
+            let caps = re.captures(line)?;
+            let app = crash_watched_app(caps.get(1)?.as_str(), watched)?;
+            let (kind, signal) = match caps.get(3) {
+                Some(trap) => ("trap", kernel_trap_signal(trap.as_str())),
+                None => ("segfault", Some("SIGSEGV")),
+            };
+            Some(CrashReport {
+                app,
+                pid: caps.get(2).and_then(|m| m.as_str().parse().ok()),
+                kind: kind.to_string(),
+                signal: signal.map(str::to_string),
+                detail: line.trim().to_string(),
+                timestamp: kernel_log_time(line)
+                    .map(|t| t.to_rfc3339())
+                    .unwrap_or_default(),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// `Get-WinEvent` rows for Application Error (1000) and Application Hang (1002).
+#[cfg(target_os = "windows")]
+fn parse_windows_app_errors(json: &str, watched: &[String]) -> Vec<CrashReport> {
+    let rows = match serde_json::from_str::<serde_json::Value>(json) {
+        Ok(serde_json::Value::Array(rows)) => rows,
+        Ok(row @ serde_json::Value::Object(_)) => vec![row],
+        _ => return Vec::new(),
+    };
+    rows.iter()
+        .filter_map(|row| {
+            let app = crash_watched_app(row.get("App")?.as_str()?, watched)?;
+            let message = row
+                .get("Message")
+                .and_then(|m| m.as_str())
+                .unwrap_or_default();
+            Some(CrashReport {
+                app,
+                pid: None,
+                kind: if row.get("Id").and_then(|i| i.as_u64()) == Some(1002) {
+                    "application_hang".to_string()
+                } else {
+                    "application_error".to_string()
+                },
+                signal: None,
+                detail: message.lines().next().unwrap_or_default().to_string(),
+                timestamp: row
+                    .get("Time")
+                    .and_then(|t| t.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+                recent_logs: message.lines().map(|l| l.to_string()).collect(),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "windows")]
+const CRASH_EVENTS_SCRIPT: &str = r#"
+$since = [DateTime]::Parse('__SINCE__')
+Get-WinEvent -FilterHashtable @{ LogName = 'Application'; Id = 1000, 1002; StartTime = $since } -ErrorAction SilentlyContinue |
+  ForEach-Object {
+    [pscustomobject]@{
+      Id = $_.Id
+      Time = $_.TimeCreated.ToUniversalTime().ToString('o')
+      App = [string]$_.Properties[0].Value
+      Message = $_.Message
+    }
+  } | ConvertTo-Json -Depth 2 -Compress
+"#;
+
+#[cfg(target_os = "windows")]
+fn detect_crashes_since(since: chrono::DateTime<Utc>, watched: &[String]) -> Vec<CrashReport> {
+    let script = CRASH_EVENTS_SCRIPT.replace("__SINCE__", &since.to_rfc3339());
+    powershell_output(&script)
+        .map(|out| parse_windows_app_errors(&out, watched))
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "linux")]
+fn detect_crashes_since(since: chrono::DateTime<Utc>, watched: &[String]) -> Vec<CrashReport> {
+    let since_arg = format!("@{}", since.timestamp());
+    let names = local_usernames();
+    let mut crashes: Vec<CrashReport> = command_stdout(
+        "coredumpctl",
+        &["list", "--json=short", "--no-pager", "--since", &since_arg],
+    )
+    .map(|(_, out)| parse_coredumps(&out, &names))
+    .unwrap_or_default()
+    .into_iter()
+    .filter_map(|dump| {
+        Some(CrashReport {
+            app: crash_watched_app(&dump.executable, watched)?,
+            pid: dump.pid,
+            kind: "coredump".to_string(),
+            signal: dump.signal.clone(),
+            detail: format!(
+                "{} dumped core ({})",
+                dump.executable,
+                dump.signal
+                    .clone()
+                    .unwrap_or_else(|| "unknown signal".to_string())
+            ),
+            timestamp: dump.timestamp.unwrap_or_default(),
+            ..Default::default()
+        })
+    })
+    .collect();
+
+    let kernel_log = read_kernel_log(&["--since", &since_arg]);
+    // The dmesg fallback returns the whole ring buffer, so drop lines from
+    // before the window (journalctl's --since has whole-second precision too).
+    let in_window = |line: &str| {
+        kernel_log_time(line)
+            .map(|t| t.timestamp() >= since.timestamp())
+            .unwrap_or(false)
+    };
+    // Without systemd-coredump the kernel line is all we get; skip it otherwise.
+    for crash in parse_kernel_crashes(&kernel_log, watched)
+        .into_iter()
+        .filter(|c| in_window(&c.detail))
+    {
+        if !crashes
+            .iter()
+            .any(|c| c.pid.is_some() && c.pid == crash.pid)
+        {
+            crashes.push(crash);
+        }
+    }
+    for oom in parse_oom_events(&kernel_log) {
+        if !in_window(&oom.message) {
+            continue;
+        }
+        let Some(app) = oom
+            .process
+            .as_deref()
+            .and_then(|p| crash_watched_app(p, watched))
+        else {
+            continue;
+        };
+        crashes.push(CrashReport {
+            app,
+            pid: oom.pid,
+            kind: "oom_kill".to_string(),
+            signal: Some("SIGKILL".to_string()),
+            detail: oom.message,
+            timestamp: oom.timestamp.unwrap_or_default(),
+            ..Default::default()
+        });
+    }
+    crashes
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn detect_crashes_since(_since: chrono::DateTime<Utc>, _watched: &[String]) -> Vec<CrashReport> {
+    Vec::new()
+}
+
+/// Focused window as "process — title"; X11 only on Linux (xdotool).
+#[cfg(target_os = "windows")]
+fn crash_foreground_window() -> Option<String> {
+    get_foreground_process_with_title()
+        .ok()
+        .map(|(process, title)| format!("{process} — {title}"))
+}
+
+#[cfg(target_os = "linux")]
+fn crash_foreground_window() -> Option<String> {
+    let (_, title) = command_stdout("xdotool", &["getactivewindow", "getwindowname"])?;
+    let title = title.trim().to_string();
+    (!title.is_empty()).then_some(title)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn crash_foreground_window() -> Option<String> {
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn crash_recent_logs() -> Vec<String> {
+    let names = local_usernames();
+    command_stdout(
+        "journalctl",
+        &[
+            "--since",
+            "-5min",
+            "-p",
+            "warning",
+            "-n",
+            "20",
+            "--no-pager",
+            "-o",
+            "short-iso",
+        ],
+    )
+    .map(|(_, out)| {
+        out.lines()
+            .filter(|l| !l.starts_with("-- "))
+            .map(|l| redact_usernames(l, &names))
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
+#[cfg(not(target_os = "linux"))]
+fn crash_recent_logs() -> Vec<String> {
+    Vec::new()
+}
+
+fn enrich_crash_report(report: &mut CrashReport, system: &mut System) {
+    system.refresh_memory();
+    system.refresh_processes();
+    report.memory_used_gb = bytes_to_gb(system.used_memory());
+    report.memory_total_gb = bytes_to_gb(system.total_memory());
+    report.top_processes = top_memory_processes(system, 5);
+    report.foreground_window = crash_foreground_window();
+    if report.recent_logs.is_empty() {
+        report.recent_logs = crash_recent_logs();
+    }
+    if report.timestamp.is_empty() {
+        report.timestamp = Utc::now().to_rfc3339();
+    }
+}
+
+fn start_crash_watcher(app: &AppHandle) {
+    static CRASH_WATCHER_STARTED: once_cell::sync::Lazy<std::sync::atomic::AtomicBool> =
+        once_cell::sync::Lazy::new(|| std::sync::atomic::AtomicBool::new(false));
+    if CRASH_WATCHER_STARTED.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        return;
+    }
+
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        let watched = crash_watch_list();
+        let mut system = System::new();
+        let mut since = Utc::now();
+        // Windows overlap by up to a second, so the previous poll's events can
+        // come back once more.
+        let mut previous: Vec<(String, String, Option<u32>, String)> = Vec::new();
+        loop {
+            sleep(Duration::from_secs(CRASH_POLL_SECS));
+            let now = Utc::now();
+            let mut seen = Vec::new();
+            for mut report in detect_crashes_since(since, &watched) {
+                let key = (
+                    report.app.clone(),
+                    report.kind.clone(),
+                    report.pid,
+                    report.timestamp.clone(),
+                );
+                let repeated = !report.timestamp.is_empty() && previous.contains(&key);
+                seen.push(key);
+                if repeated {
+                    continue;
+                }
+                enrich_crash_report(&mut report, &mut system);
+                if let Ok(mut history) = CRASH_HISTORY.lock() {
+                    history.push(report.clone());
+                    let excess = history.len().saturating_sub(CRASH_MAX_HISTORY);
+                    history.drain(..excess);
+                }
+                let _ = app_handle.emit(CRASH_EVENT, &report);
+            }
+            previous = seen;
+            since = now;
+        }
+    });
+}
+
+/// Crashes seen by the background watcher, newest first. Only terminations
+/// the OS records are reported: core dumps, kernel segfault and OOM-kill lines
+/// on Linux, Application Error/Hang events on Windows. A watched app that just
+/// exits with a non-zero status leaves no such record and is not reported.
+#[tauri::command]
+fn get_recent_crashes() -> Result<Vec<CrashReport>, String> {
+    CRASH_HISTORY
+        .lock()
+        .map(|history| history.iter().rev().cloned().collect())
+        .map_err(|_| "Crash history unavailable".to_string())
+}
+
+//
+// ───────── Displays & graphics ─────────
+//
+
+#[derive(Serialize, Default, Clone)]
+struct DisplayReport {
+    id: u32,
+    x: i32,
+    y: i32,
+    width: u32,
+    height: u32,
+    rotation: f32,
+    scale_factor: f32,
+    refresh_hz: f32,
+    is_primary: bool,
+    // Filled in when the display can be matched to a monitor below.
+    connector: Option<String>,
+    manufacturer: Option<String>,
+    model: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+struct MonitorInfo {
+    // DRM connector ("card0-HDMI-A-1") or Windows monitor instance.
+    connector: String,
+    connected: bool,
+    manufacturer: Option<String>,
+    model: Option<String>,
+    serial: Option<String>,
+    native_width: Option<u32>,
+    native_height: Option<u32>,
+    width_cm: Option<u32>,
+    height_cm: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+struct GpuInfo {
+    card: Option<String>,
+    vendor: Option<String>,
+    model: Option<String>,
+    driver: Option<String>,
+    pci_slot: Option<String>,
+}
+
+#[derive(Serialize, Default)]
+struct DisplayInventory {
+    // XDG_SESSION_TYPE on Linux ("x11", "wayland").
+    session_type: Option<String>,
+    displays: Vec<DisplayReport>,
+    monitors: Vec<MonitorInfo>,
+    gpus: Vec<GpuInfo>,
+    timestamp: String,
+}
+
+/// EDID/PNP manufacturer ids seen on typical office hardware.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn pnp_manufacturer(code: &str) -> String {
+    match code {
+        "ACR" => "Acer",
+        "AOC" => "AOC",
+        "APP" => "Apple",
+        "AUO" => "AU Optronics",
+        "AUS" => "ASUS",
+        "BNQ" => "BenQ",
+        "BOE" => "BOE",
+        "CMN" => "Innolux",
+        "DEL" => "Dell",
+        "GSM" => "LG",
+        "HPN" | "HWP" => "HP",
+        "IVM" => "iiyama",
+        "LEN" => "Lenovo",
+        "LGD" => "LG Display",
+        "NEC" => "NEC",
+        "PHL" => "Philips",
+        "SAM" => "Samsung",
+        "SHP" => "Sharp",
+        "SNY" => "Sony",
+        "VSC" => "ViewSonic",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Decodes the base EDID block: manufacturer, name/serial descriptors,
+/// physical size and the preferred (first detailed) timing.
+#[cfg(target_os = "linux")]
+fn parse_edid(edid: &[u8]) -> Option<MonitorInfo> {
+    const HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+    if edid.len() < 128 || edid[..8] != HEADER {
+        return None;
+    }
+    let id = u16::from_be_bytes([edid[8], edid[9]]);
+    let code: String = [(id >> 10) & 0x1F, (id >> 5) & 0x1F, id & 0x1F]
+        .iter()
+        .map(|c| (b'A' - 1 + *c as u8) as char)
+        .collect();
+
+    let mut info = MonitorInfo {
+        manufacturer: Some(pnp_manufacturer(&code)),
+        width_cm: (edid[21] > 0).then_some(edid[21] as u32),
+        height_cm: (edid[22] > 0).then_some(edid[22] as u32),
+        ..Default::default()
+    };
+    for block in edid[54..126].chunks(18) {
+        if block[0] != 0 || block[1] != 0 {
+            // Detailed timing; the first one is the preferred mode.
+            if info.native_width.is_none() {
+                info.native_width = Some(block[2] as u32 | ((block[4] as u32 & 0xF0) << 4));
+                info.native_height = Some(block[5] as u32 | ((block[7] as u32 & 0xF0) << 4));
+            }
+            continue;
+        }
+        let text = || {
+            String::from_utf8_lossy(&block[5..])
+                .split('\n')
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        };
+        match block[3] {
+            0xFC => info.model = Some(text()),
+            0xFF => info.serial = Some(text()),
+            _ => {}
+        }
+    }
+    if info.serial.is_none() {
+        let serial = u32::from_le_bytes([edid[12], edid[13], edid[14], edid[15]]);
+        info.serial = (serial != 0).then(|| serial.to_string());
+    }
+    Some(info)
+}
+
+/// Pairs screens with monitors: directly when there is one of each, otherwise
+/// by a unique native resolution.
+fn match_displays(displays: &mut [DisplayReport], monitors: &[MonitorInfo]) {
+    let connected: Vec<&MonitorInfo> = monitors.iter().filter(|m| m.connected).collect();
+    if displays.len() == 1 && connected.len() == 1 {
+        let monitor = connected[0];
+        let display = &mut displays[0];
+        display.connector = Some(monitor.connector.clone());
+        display.manufacturer = monitor.manufacturer.clone();
+        display.model = monitor.model.clone();
+        return;
+    }
+    for display in displays.iter_mut() {
+        let scale = if display.scale_factor > 0.0 {
+            display.scale_factor
+        } else {
+            1.0
+        };
+        let physical = (
+            (display.width as f32 * scale).round() as u32,
+            (display.height as f32 * scale).round() as u32,
+        );
+        let candidates: Vec<&&MonitorInfo> = connected
+            .iter()
+            .filter(|m| {
+                let native = (m.native_width.unwrap_or(0), m.native_height.unwrap_or(0));
+                native == (display.width, display.height) || native == physical
+            })
+            .collect();
+        if let [monitor] = candidates.as_slice() {
+            display.connector = Some(monitor.connector.clone());
+            display.manufacturer = monitor.manufacturer.clone();
+            display.model = monitor.model.clone();
+        }
+    }
+}
+
+fn screen_displays() -> Vec<DisplayReport> {
+    screenshots::Screen::all()
+        .map(|screens| {
+            screens
+                .iter()
+                .map(|screen| {
+                    let info = &screen.display_info;
+                    DisplayReport {
+                        id: info.id,
+                        x: info.x,
+                        y: info.y,
+                        width: info.width,
+                        height: info.height,
+                        rotation: info.rotation,
+                        scale_factor: info.scale_factor,
+                        refresh_hz: info.frequency,
+                        is_primary: info.is_primary,
+                        ..Default::default()
+                    }
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(target_os = "linux")]
+fn pci_vendor_name(id: &str) -> Option<String> {
+    let name = match id {
+        "0x8086" => "Intel",
+        "0x10de" => "NVIDIA",
+        "0x1002" => "AMD",
+        "0x1414" => "Microsoft (Hyper-V)",
+        "0x15ad" => "VMware",
+        "0x1af4" => "Red Hat (virtio)",
+        "0x1234" => "QEMU",
+        _ => return None,
+    };
+    Some(name.to_string())
+}
+
+/// GPUs and monitor connectors from /sys/class/drm (cardN and cardN-<connector>).
+#[cfg(target_os = "linux")]
+fn drm_inventory() -> (Vec<GpuInfo>, Vec<MonitorInfo>) {
+    let Ok(entries) = std::fs::read_dir("/sys/class/drm") else {
+        return (Vec::new(), Vec::new());
+    };
+    let pci_names = pci_device_names();
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|n| n.starts_with("card"))
+        .collect();
+    names.sort();
+
+    let mut gpus = Vec::new();
+    let mut monitors = Vec::new();
+    for name in names {
+        let path = std::path::Path::new("/sys/class/drm").join(&name);
+        if !name.contains('-') {
+            let device = path.join("device");
+            let pci_slot = read_sysfs_string(&device, "uevent").and_then(|uevent| {
+                uevent
+                    .lines()
+                    .find_map(|l| l.strip_prefix("PCI_SLOT_NAME="))
+                    .map(|s| s.to_string())
+            });
+            gpus.push(GpuInfo {
+                card: Some(name.clone()),
+                vendor: read_sysfs_string(&device, "vendor")
+                    .and_then(|id| pci_vendor_name(&id).or(Some(id))),
+                model: pci_slot
+                    .as_ref()
+                    .and_then(|slot| pci_names.get(slot).cloned()),
+                driver: std::fs::read_link(device.join("driver"))
+                    .ok()
+                    .and_then(|p| p.file_name().map(|f| f.to_string_lossy().to_string())),
+                pci_slot,
+            });
+            continue;
+        }
+
+        let connected = read_sysfs_string(&path, "status").as_deref() == Some("connected");
+        let mut monitor = std::fs::read(path.join("edid"))
+            .ok()
+            .and_then(|edid| parse_edid(&edid))
+            .unwrap_or_default();
+        monitor.connector = name.clone();
+        monitor.connected = connected;
+        if monitor.native_width.is_none() {
+            // No EDID (some laptop panels/VMs): fall back to the first listed mode.
+            let mode = read_sysfs_string(&path, "modes")
+                .and_then(|m| m.lines().next().map(|l| l.to_string()));
+            if let Some((w, h)) = mode.as_deref().and_then(|m| m.split_once('x')) {
+                monitor.native_width = w.parse().ok();
+                monitor.native_height = h
+                    .trim_end_matches(|c: char| !c.is_ascii_digit())
+                    .parse()
+                    .ok();
+            }
+        }
+        if connected {
+            monitors.push(monitor);
+        }
+    }
+    (gpus, monitors)
+}
+
+#[cfg(target_os = "windows")]
+const DISPLAY_INVENTORY_SCRIPT: &str = r#"
+$decode = { param($a) if ($a) { (($a | Where-Object { $_ -ne 0 }) | ForEach-Object { [char]$_ }) -join '' } else { $null } }
+$gpus = Get-CimInstance Win32_VideoController | ForEach-Object {
+  [pscustomobject]@{ card = $_.DeviceID; vendor = $_.AdapterCompatibility; model = $_.Name; driver = $_.DriverVersion; pci_slot = $_.PNPDeviceID }
+}
+$monitors = Get-CimInstance -Namespace root\wmi -ClassName WmiMonitorID -ErrorAction SilentlyContinue | ForEach-Object {
+  [pscustomobject]@{
+    connector = $_.InstanceName
+    connected = [bool]$_.Active
+    manufacturer = (& $decode $_.ManufacturerName)
+    model = (& $decode $_.UserFriendlyName)
+    serial = (& $decode $_.SerialNumberID)
+  }
+}
+[pscustomobject]@{ gpus = @($gpus); monitors = @($monitors) } | ConvertTo-Json -Depth 3 -Compress
+"#;
+
+#[cfg(target_os = "windows")]
+fn windows_display_hardware() -> (Vec<GpuInfo>, Vec<MonitorInfo>) {
+    let Ok(value) = run_powershell_json(DISPLAY_INVENTORY_SCRIPT) else {
+        return (Vec::new(), Vec::new());
+    };
+    let gpus: Vec<GpuInfo> = value
+        .get("gpus")
+        .cloned()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    let mut monitors: Vec<MonitorInfo> = value
+        .get("monitors")
+        .cloned()
+        .and_then(|v| serde_json::from_value(v).ok())
+        .unwrap_or_default();
+    for monitor in monitors.iter_mut() {
+        monitor.manufacturer = monitor.manufacturer.as_deref().map(pnp_manufacturer);
+    }
+    (gpus, monitors)
+}
+
+#[tauri::command]
+async fn get_display_inventory() -> Result<DisplayInventory, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let mut displays = screen_displays();
+
+        #[cfg(target_os = "linux")]
+        let (gpus, monitors) = drm_inventory();
+        #[cfg(target_os = "windows")]
+        let (gpus, monitors) = windows_display_hardware();
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+        let (gpus, monitors) = (Vec::new(), Vec::new());
+
+        match_displays(&mut displays, &monitors);
+        Ok(DisplayInventory {
+            session_type: std::env::var("XDG_SESSION_TYPE").ok(),
+            displays,
+            monitors,
+            gpus,
+            timestamp: Utc::now().to_rfc3339(),
+        })
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+//
+// ───────── USB devices & hotplug ─────────
+//
+
+#[cfg(target_os = "linux")]
+const USB_EVENT_CONNECTED: &str = "device-connected";
+#[cfg(target_os = "linux")]
+const USB_EVENT_DISCONNECTED: &str = "device-disconnected";
+#[cfg(target_os = "linux")]
+const USB_MAX_EVENTS: usize = 500;
+
+#[derive(Serialize, Default, Clone)]
+struct UsbDevice {
+    // sysfs name, e.g. "1-2.3".
+    port: String,
+    vendor_id: String,
+    product_id: String,
+    manufacturer: Option<String>,
+    product: Option<String>,
+    class: String,
+    // Negotiated speed in Mbit/s (480 = USB 2.0 high speed, 5000 = USB 3 ...).
+    speed_mbps: Option<f64>,
+    drivers: Vec<String>,
+    serial: Option<String>,
+    is_hub: bool,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct DeviceEvent {
+    // "connected" or "disconnected".
+    action: String,
+    name: String,
+    vendor_id: String,
+    product_id: String,
+    devpath: String,
+    timestamp: String,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct DeviceEventCount {
+    name: String,
+    vendor_id: String,
+    product_id: String,
+    connects: usize,
+    disconnects: usize,
+}
+
+#[derive(Serialize, Default)]
+struct DeviceHistory {
+    monitoring: bool,
+    events: Vec<DeviceEvent>,
+    // Per device, since local midnight.
+    today: Vec<DeviceEventCount>,
+}
+
+static USB_EVENTS: once_cell::sync::Lazy<std::sync::Mutex<Vec<DeviceEvent>>> =
+    once_cell::sync::Lazy::new(|| std::sync::Mutex::new(Vec::new()));
+static USB_MONITORING: once_cell::sync::Lazy<std::sync::atomic::AtomicBool> =
+    once_cell::sync::Lazy::new(|| std::sync::atomic::AtomicBool::new(false));
+
+#[cfg(target_os = "linux")]
+fn usb_class_name(code: &str) -> String {
+    match code.to_lowercase().as_str() {
+        "00" => "Per-interface",
+        "01" => "Audio",
+        "02" => "Communications",
+        "03" => "HID",
+        "05" => "Physical",
+        "06" => "Imaging",
+        "07" => "Printer",
+        "08" => "Mass storage",
+        "09" => "Hub",
+        "0a" => "CDC data",
+        "0b" => "Smart card",
+        "0d" => "Content security",
+        "0e" => "Video",
+        "0f" => "Personal healthcare",
+        "10" => "Audio/video",
+        "11" => "Billboard",
+        "dc" => "Diagnostic",
+        "e0" => "Wireless",
+        "ef" => "Miscellaneous",
+        "fe" => "Application specific",
+        "ff" => "Vendor specific",
+        other => return format!("Class {other}"),
+    }
+    .to_string()
+}
+
+#[cfg(target_os = "linux")]
+fn list_usb_devices_impl() -> Result<Vec<UsbDevice>, String> {
+    let base = std::path::Path::new("/sys/bus/usb/devices");
+    let entries =
+        std::fs::read_dir(base).map_err(|e| format!("Failed to read {}: {e}", base.display()))?;
+    let mut names: Vec<String> = entries
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    names.sort();
+
+    let mut devices = Vec::new();
+    for name in names.iter().filter(|n| !n.contains(':')) {
+        let path = base.join(name);
+        let Some(vendor_id) = read_sysfs_string(&path, "idVendor") else {
+            continue;
+        };
+        // Interfaces are "<device>:<config>.<n>"; drivers bind there.
+        let interfaces: Vec<std::path::PathBuf> = names
+            .iter()
+            .filter(|n| {
+                n.split_once(':')
+                    .map(|(dev, _)| dev == name)
+                    .unwrap_or(false)
+            })
+            .map(|n| base.join(n))
+            .collect();
+        let mut drivers: Vec<String> = interfaces
+            .iter()
+            .filter_map(|iface| std::fs::read_link(iface.join("driver")).ok())
+            .filter_map(|p| p.file_name().map(|f| f.to_string_lossy().to_string()))
+            .collect();
+        drivers.sort();
+        drivers.dedup();
+
+        let mut class = read_sysfs_string(&path, "bDeviceClass").unwrap_or_default();
+        if class == "00" {
+            // Composite device: report the first interface's class.
+            if let Some(iface_class) = interfaces
+                .first()
+                .and_then(|iface| read_sysfs_string(iface, "bInterfaceClass"))
+            {
+                class = iface_class;
+            }
+        }
+
+        devices.push(UsbDevice {
+            port: name.clone(),
+            vendor_id,
+            product_id: read_sysfs_string(&path, "idProduct").unwrap_or_default(),
+            manufacturer: read_sysfs_string(&path, "manufacturer"),
+            product: read_sysfs_string(&path, "product"),
+            is_hub: class == "09",
+            class: usb_class_name(&class),
+            speed_mbps: read_sysfs_string(&path, "speed").and_then(|s| s.parse().ok()),
+            drivers,
+            serial: read_sysfs_string(&path, "serial"),
+        });
+    }
+    Ok(devices)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn list_usb_devices_impl() -> Result<Vec<UsbDevice>, String> {
+    Err("USB inventory is only supported on Linux.".to_string())
+}
+
+/// One `udevadm monitor --property` block (KEY=value lines) as a USB device
+/// event; interfaces and other subsystems are ignored.
+#[cfg(target_os = "linux")]
+fn parse_udev_usb_event(
+    properties: &std::collections::HashMap<String, String>,
+) -> Option<DeviceEvent> {
+    if properties.get("SUBSYSTEM").map(String::as_str) != Some("usb")
+        || properties.get("DEVTYPE").map(String::as_str) != Some("usb_device")
+    {
+        return None;
+    }
+    let action = match properties.get("ACTION")?.as_str() {
+        "add" => "connected",
+        "remove" => "disconnected",
+        _ => return None,
+    };
+    // PRODUCT is "<vendor>/<product>/<bcdDevice>" in unpadded hex.
+    let mut product = properties
+        .get("PRODUCT")
+        .map(|p| {
+            p.split('/')
+                .map(|s| format!("{:0>4}", s))
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default()
+        .into_iter();
+    let vendor_id = properties
+        .get("ID_VENDOR_ID")
+        .cloned()
+        .or_else(|| product.next())
+        .unwrap_or_default();
+    let product_id = properties
+        .get("ID_MODEL_ID")
+        .cloned()
+        .or_else(|| product.next())
+        .unwrap_or_default();
+    let vendor = properties
+        .get("ID_VENDOR_FROM_DATABASE")
+        .or_else(|| properties.get("ID_VENDOR"));
+    let model = properties
+        .get("ID_MODEL_FROM_DATABASE")
+        .or_else(|| properties.get("ID_MODEL"));
+    let name = match (vendor, model) {
+        (Some(v), Some(m)) => format!("{v} {}", m.replace('_', " ")),
+        (None, Some(m)) => m.replace('_', " "),
+        (Some(v), None) => v.clone(),
+        (None, None) => format!("USB device {vendor_id}:{product_id}"),
+    };
+    Some(DeviceEvent {
+        action: action.to_string(),
+        name,
+        vendor_id,
+        product_id,
+        devpath: properties.get("DEVPATH").cloned().unwrap_or_default(),
+        timestamp: Utc::now().to_rfc3339(),
+    })
+}
+
+#[cfg(target_os = "linux")]
+fn record_device_event(app: &AppHandle, event: DeviceEvent) {
+    let name = if event.action == "connected" {
+        USB_EVENT_CONNECTED
+    } else {
+        USB_EVENT_DISCONNECTED
+    };
+    let _ = app.emit(name, &event);
+    if let Ok(mut events) = USB_EVENTS.lock() {
+        events.push(event);
+        let excess = events.len().saturating_sub(USB_MAX_EVENTS);
+        events.drain(..excess);
+    }
+}
+
+/// Follows udev's netlink feed through `udevadm monitor`, restarting it if it exits.
+#[cfg(target_os = "linux")]
+fn start_usb_monitor(app: &AppHandle) {
+    use std::io::BufRead;
+
+    if USB_MONITORING.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        return;
+    }
+    let app_handle = app.clone();
+    std::thread::spawn(move || loop {
+        let child = Command::new("udevadm")
+            .args([
+                "monitor",
+                "--udev",
+                "--property",
+                "--subsystem-match=usb/usb_device",
+            ])
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::null())
+            .spawn();
+        let Ok(mut child) = child else {
+            eprintln!("udevadm not available; USB hotplug monitoring disabled");
+            USB_MONITORING.store(false, std::sync::atomic::Ordering::SeqCst);
+            return;
+        };
+
+        if let Some(stdout) = child.stdout.take() {
+            let mut block: std::collections::HashMap<String, String> =
+                std::collections::HashMap::new();
+            for line in std::io::BufReader::new(stdout).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                if line.trim().is_empty() {
+                    if let Some(event) = parse_udev_usb_event(&block) {
+                        record_device_event(&app_handle, event);
+                    }
+                    block.clear();
+                } else if let Some((key, value)) = line.split_once('=') {
+                    block.insert(key.to_string(), value.to_string());
+                }
+            }
+        }
+        let _ = child.wait();
+        sleep(Duration::from_secs(10));
+    });
+}
+
+#[cfg(not(target_os = "linux"))]
+fn start_usb_monitor(_app: &AppHandle) {}
+
+fn count_device_events(events: &[DeviceEvent], since: &str) -> Vec<DeviceEventCount> {
+    let mut counts: Vec<DeviceEventCount> = Vec::new();
+    for event in events.iter().filter(|e| e.timestamp.as_str() >= since) {
+        let index = match counts
+            .iter()
+            .position(|c| c.vendor_id == event.vendor_id && c.product_id == event.product_id)
+        {
+            Some(i) => i,
+            None => {
+                counts.push(DeviceEventCount {
+                    name: event.name.clone(),
+                    vendor_id: event.vendor_id.clone(),
+                    product_id: event.product_id.clone(),
+                    ..Default::default()
+                });
+                counts.len() - 1
+            }
+        };
+        if event.action == "connected" {
+            counts[index].connects += 1;
+        } else {
+            counts[index].disconnects += 1;
+        }
+    }
+    counts.sort_by_key(|c| std::cmp::Reverse(c.disconnects));
+    counts
+}
+
+#[tauri::command]
+fn get_usb_devices() -> Result<Vec<UsbDevice>, String> {
+    list_usb_devices_impl()
+}
+
+#[tauri::command]
+fn get_device_history() -> Result<DeviceHistory, String> {
+    let events = USB_EVENTS
+        .lock()
+        .map(|events| events.clone())
+        .map_err(|_| "Device history unavailable".to_string())?;
+    // Timestamps are RFC 3339 UTC, so local midnight converted to UTC compares as text.
+    let midnight = chrono::Local::now()
+        .date_naive()
+        .and_hms_opt(0, 0, 0)
+        .and_then(|t| t.and_local_timezone(chrono::Local).earliest())
+        .map(|t| t.with_timezone(&Utc).to_rfc3339())
+        .unwrap_or_default();
+    Ok(DeviceHistory {
+        monitoring: USB_MONITORING.load(std::sync::atomic::Ordering::SeqCst),
+        today: count_device_events(&events, &midnight),
+        events: events.into_iter().rev().collect(),
+    })
+}
+
+//
+// ───────── Audio diagnostics ─────────
+//
+
+#[cfg(target_os = "linux")]
+const AUDIO_TEST_RATE: u32 = 16_000;
+#[cfg(target_os = "linux")]
+const AUDIO_TEST_MILLIS: u64 = 1_000;
+// Peak below this (percent of full scale) counts as silence.
+#[cfg(target_os = "linux")]
+const AUDIO_SILENCE_PERCENT: f64 = 1.0;
+#[cfg(target_os = "linux")]
+const AUDIO_LOW_VOLUME_PERCENT: u32 = 20;
+
+#[derive(Serialize, Default, Clone)]
+struct AudioDevice {
+    name: String,
+    description: String,
+    // "sink" (output) or "source" (input).
+    kind: String,
+    is_default: bool,
+    // Sources that mirror a sink's output rather than a microphone.
+    is_monitor: bool,
+    state: String,
+    muted: bool,
+    volume_percent: Option<u32>,
+    sample_format: Option<String>,
+    sample_rate_hz: Option<u32>,
+    channels: Option<u32>,
+    active_port: Option<String>,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct AudioStream {
+    // "playback" or "capture".
+    direction: String,
+    application: String,
+    binary: Option<String>,
+    pid: Option<u32>,
+    device: Option<String>,
+    muted: bool,
+    corked: bool,
+    volume_percent: Option<u32>,
+}
+
+#[derive(Serialize, Default)]
+struct AudioDiagnostics {
+    server: Option<String>,
+    default_sink: Option<String>,
+    default_source: Option<String>,
+    sinks: Vec<AudioDevice>,
+    sources: Vec<AudioDevice>,
+    streams: Vec<AudioStream>,
+    warnings: Vec<String>,
+    timestamp: String,
+}
+
+#[derive(Serialize, Default)]
+struct InputLevelTest {
+    source: String,
+    duration_ms: u64,
+    peak_percent: f64,
+    peak_dbfs: Option<f64>,
+    signal_detected: bool,
+}
+
+/// Average of the per-channel `value_percent` strings ("65%").
+#[cfg(target_os = "linux")]
+fn pactl_volume_percent(volume: Option<&serde_json::Value>) -> Option<u32> {
+    let channels: Vec<u32> = volume?
+        .as_object()?
+        .values()
+        .filter_map(|ch| ch.get("value_percent")?.as_str())
+        .filter_map(|p| p.trim().trim_end_matches('%').parse().ok())
+        .collect();
+    if channels.is_empty() {
+        return None;
+    }
+    Some(channels.iter().sum::<u32>() / channels.len() as u32)
+}
+
+/// `sample_specification` such as "s16le 2ch 48000Hz".
+#[cfg(target_os = "linux")]
+fn parse_sample_spec(spec: &str) -> (Option<String>, Option<u32>, Option<u32>) {
+    let mut format = None;
+    let mut channels = None;
+    let mut rate = None;
+    for part in spec.split_whitespace() {
+        if let Some(ch) = part.strip_suffix("ch") {
+            channels = ch.parse().ok();
+        } else if let Some(hz) = part.strip_suffix("Hz") {
+            rate = hz.parse().ok();
+        } else {
+            format = Some(part.to_string());
+        }
+    }
+    (format, rate, channels)
+}
+
+/// `pactl --format=json list sinks|sources`.
+#[cfg(target_os = "linux")]
+fn parse_pactl_devices(json: &str, kind: &str, default: Option<&str>) -> Vec<AudioDevice> {
+    let Ok(serde_json::Value::Array(items)) = serde_json::from_str::<serde_json::Value>(json)
+    else {
+        return Vec::new();
+    };
+    items
+        .iter()
+        .filter_map(|item| {
+            let name = item.get("name")?.as_str()?.to_string();
+            let (sample_format, sample_rate_hz, channels) = parse_sample_spec(
+                item.get("sample_specification")
+                    .and_then(|s| s.as_str())
+                    .unwrap_or_default(),
+            );
+            Some(AudioDevice {
+                is_default: default == Some(name.as_str()),
+                is_monitor: item
+                    .get("monitor_of_sink")
+                    .and_then(|m| m.as_str())
+                    .map(|m| !m.is_empty() && m != "n/a")
+                    .unwrap_or(false),
+                description: item
+                    .get("description")
+                    .and_then(|d| d.as_str())
+                    .unwrap_or(&name)
+                    .to_string(),
+                kind: kind.to_string(),
+                state: item
+                    .get("state")
+                    .and_then(|s| s.as_str())
+                    .unwrap_or_default()
+                    .to_lowercase(),
+                muted: item.get("mute").and_then(|m| m.as_bool()).unwrap_or(false),
+                volume_percent: pactl_volume_percent(item.get("volume")),
+                sample_format,
+                sample_rate_hz,
+                channels,
+                active_port: item
+                    .get("active_port")
+                    .and_then(|p| p.as_str())
+                    .map(|p| p.to_string()),
+                name,
+            })
+        })
+        .collect()
+}
+
+/// `pactl --format=json list sink-inputs|source-outputs`; `devices` maps
+/// sink/source indices to names.
+#[cfg(target_os = "linux")]
+fn parse_pactl_streams(
+    json: &str,
+    direction: &str,
+    devices: &std::collections::HashMap<u64, String>,
+) -> Vec<AudioStream> {
+    let Ok(serde_json::Value::Array(items)) = serde_json::from_str::<serde_json::Value>(json)
+    else {
+        return Vec::new();
+    };
+    let device_key = if direction == "playback" {
+        "sink"
+    } else {
+        "source"
+    };
+    items
+        .iter()
+        .map(|item| {
+            let prop = |key: &str| {
+                item.get("properties")
+                    .and_then(|p| p.get(key))
+                    .and_then(|v| v.as_str())
+                    .map(|v| v.to_string())
+            };
+            AudioStream {
+                direction: direction.to_string(),
+                application: prop("application.name")
+                    .or_else(|| prop("media.name"))
+                    .unwrap_or_else(|| "unknown".to_string()),
+                binary: prop("application.process.binary"),
+                pid: prop("application.process.id").and_then(|p| p.parse().ok()),
+                device: item
+                    .get(device_key)
+                    .and_then(|d| d.as_u64())
+                    .and_then(|idx| devices.get(&idx).cloned()),
+                muted: item.get("mute").and_then(|m| m.as_bool()).unwrap_or(false),
+                corked: item
+                    .get("corked")
+                    .and_then(|c| c.as_bool())
+                    .unwrap_or(false),
+                volume_percent: pactl_volume_percent(item.get("volume")),
+            }
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn pactl_index_names(json: &str) -> std::collections::HashMap<u64, String> {
+    serde_json::from_str::<serde_json::Value>(json)
+        .ok()
+        .and_then(|v| v.as_array().cloned())
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|item| {
+            Some((
+                item.get("index")?.as_u64()?,
+                item.get("name")?.as_str()?.to_string(),
+            ))
+        })
+        .collect()
+}
+
+/// Peak of signed 16-bit little-endian samples, as percent of full scale and dBFS.
+#[cfg(target_os = "linux")]
+fn pcm_s16le_peak(pcm: &[u8]) -> (f64, Option<f64>) {
+    let peak = pcm
+        .chunks_exact(2)
+        .map(|s| (i16::from_le_bytes([s[0], s[1]]) as i32).unsigned_abs())
+        .max()
+        .unwrap_or(0);
+    let ratio = peak as f64 / 32768.0;
+    let dbfs = (peak > 0).then(|| (20.0 * ratio.log10() * 10.0).round() / 10.0);
+    ((ratio * 1000.0).round() / 10.0, dbfs)
+}
+
+#[cfg(target_os = "linux")]
+fn audio_warnings(report: &AudioDiagnostics) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let microphones: Vec<&AudioDevice> = report.sources.iter().filter(|s| !s.is_monitor).collect();
+    if microphones.is_empty() {
+        warnings.push("No microphone (input source) found".to_string());
+    }
+    if let Some(mic) = microphones.iter().find(|s| s.is_default) {
+        if mic.muted {
+            warnings.push(format!(
+                "Default microphone \"{}\" is muted",
+                mic.description
+            ));
+        } else if mic.volume_percent.unwrap_or(100) < AUDIO_LOW_VOLUME_PERCENT {
+            warnings.push(format!(
+                "Default microphone \"{}\" volume is {}%",
+                mic.description,
+                mic.volume_percent.unwrap_or(0)
+            ));
+        }
+    } else if report.sources.iter().any(|s| s.is_default && s.is_monitor) {
+        warnings.push("Default input is a monitor of an output, not a microphone".to_string());
+    }
+    if let Some(sink) = report.sinks.iter().find(|s| s.is_default && s.muted) {
+        warnings.push(format!("Default output \"{}\" is muted", sink.description));
+    }
+    warnings
+}
+
+#[cfg(target_os = "linux")]
+fn pactl_json(args: &[&str]) -> Option<String> {
+    let mut all = vec!["--format=json"];
+    all.extend_from_slice(args);
+    command_stdout("pactl", &all)
+        .filter(|(code, _)| *code == 0)
+        .map(|(_, out)| out)
+}
+
+#[cfg(target_os = "linux")]
+fn get_audio_diagnostics_linux() -> Result<AudioDiagnostics, String> {
+    let info: serde_json::Value = pactl_json(&["info"])
+        .and_then(|out| serde_json::from_str(&out).ok())
+        .ok_or_else(|| "Could not reach PulseAudio/PipeWire (pactl info failed)".to_string())?;
+    let text = |key: &str| {
+        info.get(key)
+            .and_then(|v| v.as_str())
+            .map(|v| v.to_string())
+    };
+    let default_sink = text("default_sink_name");
+    let default_source = text("default_source_name");
+
+    let sinks_json = pactl_json(&["list", "sinks"]).unwrap_or_default();
+    let sources_json = pactl_json(&["list", "sources"]).unwrap_or_default();
+    let mut streams = parse_pactl_streams(
+        &pactl_json(&["list", "sink-inputs"]).unwrap_or_default(),
+        "playback",
+        &pactl_index_names(&sinks_json),
+    );
+    streams.extend(parse_pactl_streams(
+        &pactl_json(&["list", "source-outputs"]).unwrap_or_default(),
+        "capture",
+        &pactl_index_names(&sources_json),
+    ));
+
+    let mut report = AudioDiagnostics {
+        server: text("server_name").map(|name| match text("server_version") {
+            Some(version) => format!("{name} {version}"),
+            None => name,
+        }),
+        sinks: parse_pactl_devices(&sinks_json, "sink", default_sink.as_deref()),
+        sources: parse_pactl_devices(&sources_json, "source", default_source.as_deref()),
+        default_sink,
+        default_source,
+        streams,
+        warnings: Vec::new(),
+        timestamp: Utc::now().to_rfc3339(),
+    };
+    report.warnings = audio_warnings(&report);
+    Ok(report)
+}
+
+/// Records ~1s of mono audio with `parec` and measures the peak.
+#[cfg(target_os = "linux")]
+fn test_input_level_linux(source: Option<String>) -> Result<InputLevelTest, String> {
+    use std::io::Read;
+
+    let source = match source.filter(|s| !s.trim().is_empty()) {
+        Some(source) => source,
+        None => pactl_json(&["info"])
+            .and_then(|out| serde_json::from_str::<serde_json::Value>(&out).ok())
+            .and_then(|info| {
+                info.get("default_source_name")?
+                    .as_str()
+                    .map(|s| s.to_string())
+            })
+            .ok_or_else(|| "No default input device".to_string())?,
+    };
+    let bytes_wanted = (AUDIO_TEST_RATE as u64 * 2 * AUDIO_TEST_MILLIS / 1000) as usize;
+
+    let mut child = Command::new("parec")
+        .args([
+            &format!("--device={source}"),
+            "--format=s16le",
+            "--channels=1",
+            &format!("--rate={AUDIO_TEST_RATE}"),
+            "--raw",
+        ])
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to run parec: {e}"))?;
+    let mut stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| "parec produced no output".to_string())?;
+
+    // parec streams in real time; give up if the device delivers nothing.
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let mut pcm = Vec::with_capacity(bytes_wanted);
+        let _ = (&mut stdout)
+            .take(bytes_wanted as u64)
+            .read_to_end(&mut pcm);
+        let _ = tx.send(pcm);
+    });
+    let pcm = rx.recv_timeout(Duration::from_millis(AUDIO_TEST_MILLIS * 3));
+    let _ = child.kill();
+    let _ = child.wait();
+    let pcm = pcm.map_err(|_| format!("No audio received from {source}"))?;
+
+    let (peak_percent, peak_dbfs) = pcm_s16le_peak(&pcm);
+    Ok(InputLevelTest {
+        source,
+        duration_ms: pcm.len() as u64 * 1000 / (AUDIO_TEST_RATE as u64 * 2),
+        peak_percent,
+        peak_dbfs,
+        signal_detected: peak_percent >= AUDIO_SILENCE_PERCENT,
+    })
+}
+
+#[tauri::command]
+async fn get_audio_diagnostics() -> Result<AudioDiagnostics, String> {
+    #[cfg(target_os = "linux")]
+    {
+        tauri::async_runtime::spawn_blocking(get_audio_diagnostics_linux)
+            .await
+            .map_err(|e| format!("Thread join error: {e}"))?
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("Audio diagnostics are only supported on Linux.".to_string())
+    }
+}
+
+#[tauri::command]
+async fn test_microphone_level(source: Option<String>) -> Result<InputLevelTest, String> {
+    #[cfg(target_os = "linux")]
+    {
+        tauri::async_runtime::spawn_blocking(move || test_input_level_linux(source))
+            .await
+            .map_err(|e| format!("Thread join error: {e}"))?
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = source;
+        Err("Microphone test is only supported on Linux.".to_string())
+    }
+}
+
+//
+// ───────── Camera diagnostics ─────────
+//
+
+#[cfg(target_os = "linux")]
+const CAMERA_FRAME_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Serialize, Default, Clone)]
+struct CameraFormat {
+    fourcc: String,
+    description: String,
+    // "1280x720@30" style entries.
+    sizes: Vec<String>,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct CameraHolder {
+    pid: u32,
+    name: String,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct CameraDevice {
+    device: String,
+    name: String,
+    driver: Option<String>,
+    bus_info: Option<String>,
+    usb_id: Option<String>,
+    capabilities: Vec<String>,
+    // False for metadata-only nodes that UVC cameras expose next to the real one.
+    is_capture: bool,
+    formats: Vec<CameraFormat>,
+    held_by: Vec<CameraHolder>,
+}
+
+#[derive(Serialize, Default)]
+struct CameraDiagnostics {
+    devices: Vec<CameraDevice>,
+    // Capabilities and formats need v4l2-ctl (v4l-utils).
+    v4l2_ctl_available: bool,
+    timestamp: String,
+}
+
+/// Runs `command` and kills it if it hasn't finished within `timeout`.
+fn output_with_timeout(
+    mut command: Command,
+    timeout: Duration,
+) -> Result<std::process::Output, String> {
+    use std::io::Read;
+
+    let mut child = command
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start process: {e}"))?;
+    let reader = |pipe: Option<Box<dyn Read + Send>>| {
+        std::thread::spawn(move || {
+            let mut buf = Vec::new();
+            if let Some(mut pipe) = pipe {
+                let _ = pipe.read_to_end(&mut buf);
+            }
+            buf
+        })
+    };
+    let stdout = reader(
+        child
+            .stdout
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    );
+    let stderr = reader(
+        child
+            .stderr
+            .take()
+            .map(|p| Box::new(p) as Box<dyn Read + Send>),
+    );
+
+    let deadline = std::time::Instant::now() + timeout;
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if std::time::Instant::now() < deadline => sleep(Duration::from_millis(100)),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("Timed out after {}s", timeout.as_secs()));
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+    };
+    Ok(std::process::Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
+/// `v4l2-ctl --info`: driver, bus and the "Device Caps" list (falls back to
+/// the overall "Capabilities" block).
+#[cfg(target_os = "linux")]
+fn parse_v4l2_info(output: &str) -> (Option<String>, Option<String>, Option<String>, Vec<String>) {
+    let mut driver = None;
+    let mut card = None;
+    let mut bus = None;
+    let mut caps: Vec<String> = Vec::new();
+    let mut device_caps: Vec<String> = Vec::new();
+    let mut section = "";
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some((key, value)) = trimmed.split_once(':') {
+            let value = value.trim();
+            match key.trim() {
+                "Driver name" => driver = Some(value.to_string()),
+                "Card type" => card = Some(value.to_string()),
+                "Bus info" => bus = Some(value.to_string()),
+                "Capabilities" => section = "caps",
+                "Device Caps" => section = "device",
+                _ => section = "",
+            }
+            continue;
+        }
+        if trimmed.is_empty() || !line.starts_with(['\t', ' ']) {
+            continue;
+        }
+        match section {
+            "caps" => caps.push(trimmed.to_string()),
+            "device" => device_caps.push(trimmed.to_string()),
+            _ => {}
+        }
+    }
+    let capabilities = if device_caps.is_empty() {
+        caps
+    } else {
+        device_caps
+    };
+    (driver, card, bus, capabilities)
+}
+
+/// `v4l2-ctl --list-formats-ext`.
+#[cfg(target_os = "linux")]
+fn parse_v4l2_formats(output: &str) -> Vec<CameraFormat> {
+    let Ok(format_re) = regex::Regex::new(r"\[\d+\]:\s*'([^']+)'\s*\(([^)]*)") else {
+        return Vec::new();
+    };
+    let mut formats: Vec<CameraFormat> = Vec::new();
+    for line in output.lines().map(str::trim) {
+        if let Some(caps) = format_re.captures(line) {
+            formats.push(CameraFormat {
+                fourcc: caps[1].to_string(),
+                description: caps[2].trim_end_matches(", compressed").to_string(),
+                sizes: Vec::new(),
+            });
+        } else if let Some(size) = line.strip_prefix("Size: Discrete ") {
+            if let Some(format) = formats.last_mut() {
+                format.sizes.push(size.trim().to_string());
+            }
+        } else if let Some(interval) = line.strip_prefix("Interval: Discrete ") {
+            // "0.033s (30.000 fps)" -> append the best rate to the last size once.
+            let fps = interval
+                .split_once('(')
+                .and_then(|(_, rest)| rest.split_whitespace().next())
+                .and_then(|f| f.parse::<f64>().ok());
+            if let (Some(fps), Some(size)) =
+                (fps, formats.last_mut().and_then(|f| f.sizes.last_mut()))
+            {
+                if !size.contains('@') {
+                    size.push_str(&format!("@{}", fps.round()));
+                }
+            }
+        }
+    }
+    formats
+}
+
+/// Processes with an open descriptor on `device` (only those we may inspect).
+#[cfg(target_os = "linux")]
+fn device_holders(device: &std::path::Path) -> Vec<CameraHolder> {
+    let Ok(procs) = std::fs::read_dir("/proc") else {
+        return Vec::new();
+    };
+    let own_pid = std::process::id();
+    procs
+        .flatten()
+        .filter_map(|entry| {
+            let pid: u32 = entry.file_name().to_string_lossy().parse().ok()?;
+            if pid == own_pid {
+                return None;
+            }
+            let holds = std::fs::read_dir(entry.path().join("fd"))
+                .ok()?
+                .flatten()
+                .any(|fd| {
+                    std::fs::read_link(fd.path())
+                        .map(|t| t == device)
+                        .unwrap_or(false)
+                });
+            holds.then(|| CameraHolder {
+                pid,
+                name: std::fs::read_to_string(entry.path().join("comm"))
+                    .map(|c| c.trim().to_string())
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn get_camera_diagnostics_linux() -> CameraDiagnostics {
+    let mut nodes: Vec<String> = std::fs::read_dir("/sys/class/video4linux")
+        .map(|entries| {
+            entries
+                .flatten()
+                .map(|e| e.file_name().to_string_lossy().to_string())
+                .filter(|n| n.starts_with("video"))
+                .collect()
+        })
+        .unwrap_or_default();
+    nodes.sort_by_key(|n| {
+        n.trim_start_matches("video")
+            .parse::<u32>()
+            .unwrap_or(u32::MAX)
+    });
+    let v4l2_ctl_available = command_exists("v4l2-ctl");
+
+    let devices = nodes
+        .into_iter()
+        .map(|node| {
+            let sys = std::path::Path::new("/sys/class/video4linux").join(&node);
+            let device = format!("/dev/{node}");
+            // The USB device is two levels above the interface the node hangs off.
+            let usb_dir = sys.join("device").join("..");
+            let usb_id = read_sysfs_string(&usb_dir, "idVendor").map(|vendor| {
+                format!(
+                    "{vendor}:{}",
+                    read_sysfs_string(&usb_dir, "idProduct").unwrap_or_default()
+                )
+            });
+            let mut camera = CameraDevice {
+                name: read_sysfs_string(&sys, "name").unwrap_or_else(|| node.clone()),
+                usb_id,
+                held_by: device_holders(std::path::Path::new(&device)),
+                ..Default::default()
+            };
+            if v4l2_ctl_available {
+                if let Some((_, info)) = command_stdout("v4l2-ctl", &["-d", &device, "--info"]) {
+                    let (driver, card, bus, capabilities) = parse_v4l2_info(&info);
+                    camera.driver = driver;
+                    camera.bus_info = bus;
+                    camera.name = card.unwrap_or(camera.name);
+                    camera.is_capture = capabilities.iter().any(|c| c == "Video Capture");
+                    camera.capabilities = capabilities;
+                }
+                if camera.is_capture {
+                    camera.formats =
+                        command_stdout("v4l2-ctl", &["-d", &device, "--list-formats-ext"])
+                            .map(|(_, out)| parse_v4l2_formats(&out))
+                            .unwrap_or_default();
+                }
+            } else {
+                // sysfs index 0 is the capture node on UVC cameras.
+                camera.is_capture = read_sysfs_string(&sys, "index").as_deref() == Some("0");
+            }
+            camera.device = device;
+            camera
+        })
+        .collect();
+
+    CameraDiagnostics {
+        devices,
+        v4l2_ctl_available,
+        timestamp: Utc::now().to_rfc3339(),
+    }
+}
+
+#[tauri::command]
+async fn get_camera_diagnostics() -> Result<CameraDiagnostics, String> {
+    #[cfg(target_os = "linux")]
+    {
+        tauri::async_runtime::spawn_blocking(|| Ok(get_camera_diagnostics_linux()))
+            .await
+            .map_err(|e| format!("Thread join error: {e}"))?
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        Err("Camera diagnostics are only supported on Linux.".to_string())
+    }
+}
+
+/// Grabs one frame with ffmpeg and returns it as a base64 PNG.
+#[tauri::command]
+async fn capture_camera_test_frame(device: String) -> Result<String, String> {
+    #[cfg(target_os = "linux")]
+    {
+        tauri::async_runtime::spawn_blocking(move || {
+            if !device.starts_with("/dev/video") {
+                return Err(format!("Not a camera device: {device}"));
+            }
+            let mut command = Command::new("ffmpeg");
+            command.args([
+                "-hide_banner",
+                "-loglevel",
+                "error",
+                "-f",
+                "v4l2",
+                "-i",
+                &device,
+                "-frames:v",
+                "1",
+                "-f",
+                "image2pipe",
+                "-vcodec",
+                "png",
+                "-",
+            ]);
+            let output =
+                output_with_timeout(command, Duration::from_secs(CAMERA_FRAME_TIMEOUT_SECS))
+                    .map_err(|e| format!("Camera test failed: {e}"))?;
+            if !output.status.success() || output.stdout.is_empty() {
+                return Err(format!(
+                    "Camera test failed: {}",
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+            Ok(general_purpose::STANDARD.encode(&output.stdout))
+        })
+        .await
+        .map_err(|e| format!("Thread join error: {e}"))?
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = device;
+        Err("Camera test is only supported on Linux.".to_string())
+    }
+}
+
+//
+// ───────── Certificates ─────────
+//
+
+// Comma-separated internal root CAs to look for, by subject CN or SHA-256 fingerprint.
+const REQUIRED_CAS_ENV: &str = "GOLPAC_REQUIRED_CAS";
+const CERT_DEFAULT_EXPIRY_DAYS: u32 = 30;
+#[cfg(target_os = "linux")]
+const CERT_SYSTEM_BUNDLES: [&str; 4] = [
+    "/etc/ssl/certs/ca-certificates.crt",
+    "/etc/pki/tls/certs/ca-bundle.crt",
+    "/etc/ssl/ca-bundle.pem",
+    "/etc/pki/ca-trust/extracted/pem/tls-ca-bundle.pem",
+];
+// Admin-added CAs and the tool that merges them into the bundle.
+#[cfg(target_os = "linux")]
+const CERT_LOCAL_ANCHORS: [(&str, &str); 2] = [
+    ("/usr/local/share/ca-certificates", "update-ca-certificates"),
+    ("/etc/pki/ca-trust/source/anchors", "update-ca-trust"),
+];
+
+#[derive(Serialize, Deserialize, Default, Clone)]
+#[serde(default)]
+struct CertificateInfo {
+    store: String,
+    subject: String,
+    issuer: String,
+    common_name: Option<String>,
+    not_after: Option<String>,
+    days_left: Option<i64>,
+    fingerprint_sha256: String,
+    // NSS trust flags ("CT,C,C"); None for plain trust stores.
+    trust: Option<String>,
+    trusted_root: bool,
+    has_private_key: bool,
+    // e.g. a locally added CA that is not in the system bundle yet.
+    note: Option<String>,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct CertificateStore {
+    name: String,
+    path: String,
+    count: usize,
+    error: Option<String>,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct RequiredCaStatus {
+    name: String,
+    installed: bool,
+    trusted: bool,
+    stores: Vec<String>,
+    days_left: Option<i64>,
+}
+
+#[derive(Serialize, Default)]
+struct CertificateReport {
+    within_days: u32,
+    stores: Vec<CertificateStore>,
+    // Expired or expiring within `within_days`.
+    expiring: Vec<CertificateInfo>,
+    // Client certificates and locally added CAs.
+    user_certificates: Vec<CertificateInfo>,
+    required_cas: Vec<RequiredCaStatus>,
+    timestamp: String,
+}
+
+fn required_cas() -> Vec<String> {
+    std::env::var(REQUIRED_CAS_ENV)
+        .unwrap_or_default()
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn normalize_fingerprint(value: &str) -> String {
+    value
+        .chars()
+        .filter(|c| c.is_ascii_hexdigit())
+        .collect::<String>()
+        .to_uppercase()
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn certificate_days_left(not_after: &chrono::DateTime<Utc>) -> i64 {
+    (*not_after - Utc::now()).num_days()
+}
+
+/// Matches a configured CA against a certificate's fingerprint or CN.
+fn certificate_matches(cert: &CertificateInfo, wanted: &str) -> bool {
+    let fingerprint = normalize_fingerprint(wanted);
+    if fingerprint.len() == 64 {
+        return normalize_fingerprint(&cert.fingerprint_sha256) == fingerprint;
+    }
+    cert.common_name
+        .as_deref()
+        .map(|cn| cn.eq_ignore_ascii_case(wanted))
+        .unwrap_or(false)
+}
+
+fn required_ca_status(certs: &[CertificateInfo], wanted: &[String]) -> Vec<RequiredCaStatus> {
+    wanted
+        .iter()
+        .map(|name| {
+            let matches: Vec<&CertificateInfo> = certs
+                .iter()
+                .filter(|c| certificate_matches(c, name))
+                .collect();
+            let mut stores: Vec<String> = Vec::new();
+            for cert in &matches {
+                if !stores.contains(&cert.store) {
+                    stores.push(cert.store.clone());
+                }
+            }
+            RequiredCaStatus {
+                name: name.clone(),
+                installed: !matches.is_empty(),
+                trusted: matches
+                    .iter()
+                    .any(|c| c.trusted_root && c.days_left.map(|d| d >= 0).unwrap_or(true)),
+                stores,
+                days_left: matches.iter().filter_map(|c| c.days_left).max(),
+            }
+        })
+        .collect()
+}
+
+/// Reads one DER TLV, returning (tag, contents, remainder).
+fn der_next(input: &[u8]) -> Option<(u8, &[u8], &[u8])> {
+    let (&tag, rest) = input.split_first()?;
+    let (&first, mut rest) = rest.split_first()?;
+    let len = if first & 0x80 == 0 {
+        first as usize
+    } else {
+        let n = (first & 0x7f) as usize;
+        if n == 0 || n > 4 || rest.len() < n {
+            return None;
+        }
+        let len = rest[..n]
+            .iter()
+            .fold(0usize, |acc, b| (acc << 8) | *b as usize);
+        rest = &rest[n..];
+        len
+    };
+    (rest.len() >= len).then(|| (tag, &rest[..len], &rest[len..]))
+}
+
+fn der_time(tag: u8, bytes: &[u8]) -> Option<chrono::DateTime<Utc>> {
+    let text = std::str::from_utf8(bytes).ok()?;
+    let format = match tag {
+        0x17 => "%y%m%d%H%M%SZ",
+        0x18 => "%Y%m%d%H%M%SZ",
+        _ => return None,
+    };
+    chrono::NaiveDateTime::parse_from_str(text, format)
+        .ok()
+        .map(|t| t.and_utc())
+}
+
+/// DER of each certificate in a PEM bundle.
+#[cfg(target_os = "linux")]
+fn pem_certificates(text: &str) -> Vec<Result<Vec<u8>, String>> {
+    const BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+    const END: &str = "-----END CERTIFICATE-----";
+    text.split(BEGIN)
+        .skip(1)
+        .map(|rest| {
+            let body: String = rest
+                .split(END)
+                .next()
+                .unwrap_or_default()
+                .split_whitespace()
+                .collect();
+            general_purpose::STANDARD
+                .decode(body)
+                .map_err(|e| e.to_string())
+        })
+        .collect()
+}
+
+/// A DirectoryString / IA5String attribute value.
+#[cfg(target_os = "linux")]
+fn der_string(tag: u8, bytes: &[u8]) -> String {
+    match tag {
+        // BMPString
+        0x1e => String::from_utf16_lossy(
+            &bytes
+                .chunks_exact(2)
+                .map(|pair| u16::from_be_bytes([pair[0], pair[1]]))
+                .collect::<Vec<_>>(),
+        ),
+        _ => String::from_utf8_lossy(bytes).to_string(),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn der_oid(bytes: &[u8]) -> String {
+    let mut arcs: Vec<u64> = Vec::new();
+    let mut value = 0u64;
+    for b in bytes {
+        value = (value << 7) | (b & 0x7f) as u64;
+        if b & 0x80 == 0 {
+            if arcs.is_empty() {
+                // The first subidentifier packs the first two arcs.
+                let first = (value / 40).min(2);
+                arcs.extend([first, value - first * 40]);
+            } else {
+                arcs.push(value);
+            }
+            value = 0;
+        }
+    }
+    arcs.iter()
+        .map(u64::to_string)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// An X.501 Name in RFC 2253 form (most specific RDN first, like
+/// `openssl -nameopt RFC2253`) plus its common name.
+#[cfg(target_os = "linux")]
+fn der_name(name: &[u8]) -> (String, Option<String>) {
+    let mut rdns = Vec::new();
+    let mut common_name = None;
+    let mut rest = name;
+    while let Some((_, set, next)) = der_next(rest) {
+        rest = next;
+        let mut attributes = Vec::new();
+        let mut set_rest = set;
+        while let Some((_, attribute, next)) = der_next(set_rest) {
+            set_rest = next;
+            let Some((_, oid, value)) = der_next(attribute) else {
+                continue;
+            };
+            let Some((tag, value, _)) = der_next(value) else {
+                continue;
+            };
+            let value = der_string(tag, value);
+            let key = match der_oid(oid).as_str() {
+                "2.5.4.3" => "CN".to_string(),
+                "2.5.4.6" => "C".to_string(),
+                "2.5.4.7" => "L".to_string(),
+                "2.5.4.8" => "ST".to_string(),
+                "2.5.4.9" => "STREET".to_string(),
+                "2.5.4.10" => "O".to_string(),
+                "2.5.4.11" => "OU".to_string(),
+                "0.9.2342.19200300.100.1.25" => "DC".to_string(),
+                "1.2.840.113549.1.9.1" => "emailAddress".to_string(),
+                other => other.to_string(),
+            };
+            if key == "CN" {
+                common_name = Some(value.clone());
+            }
+            let mut escaped = String::new();
+            for c in value.chars() {
+                if matches!(c, ',' | '+' | '"' | '\\' | '<' | '>' | ';') {
+                    escaped.push('\\');
+                }
+                escaped.push(c);
+            }
+            attributes.push(format!("{key}={escaped}"));
+        }
+        rdns.push(attributes.join("+"));
+    }
+    rdns.reverse();
+    (rdns.join(","), common_name)
+}
+
+/// Subject, issuer, notAfter and SHA-256 fingerprint of a DER certificate.
+#[cfg(target_os = "linux")]
+fn parse_der_certificate(der: &[u8], store: &str) -> Option<CertificateInfo> {
+    use sha2::Digest;
+
+    let (_, cert, _) = der_next(der)?;
+    let (_, tbs, _) = der_next(cert)?;
+    let mut rest = tbs;
+    let (tag, _, after_version) = der_next(rest)?;
+    if tag == 0xa0 {
+        rest = after_version;
+    }
+    let (_, _, rest) = der_next(rest)?; // serialNumber
+    let (_, _, rest) = der_next(rest)?; // signature
+    let (_, issuer, rest) = der_next(rest)?;
+    let (_, validity, rest) = der_next(rest)?;
+    let (_, subject, _) = der_next(rest)?;
+    let (_, _, validity) = der_next(validity)?; // notBefore
+    let (tag, not_after, _) = der_next(validity)?;
+    let not_after = der_time(tag, not_after);
+
+    let (subject, common_name) = der_name(subject);
+    Some(CertificateInfo {
+        store: store.to_string(),
+        subject,
+        issuer: der_name(issuer).0,
+        common_name,
+        not_after: not_after.map(|t| t.to_rfc3339()),
+        days_left: not_after.as_ref().map(certificate_days_left),
+        fingerprint_sha256: sha2::Sha256::digest(der)
+            .iter()
+            .map(|b| format!("{b:02X}"))
+            .collect::<Vec<_>>()
+            .join(":"),
+        ..Default::default()
+    })
+}
+
+/// Parses every certificate in a PEM text, counting the blocks that fail.
+#[cfg(target_os = "linux")]
+fn parse_pem_certificates(text: &str, store: &str) -> (Vec<CertificateInfo>, usize) {
+    let mut failed = 0;
+    let certs = pem_certificates(text)
+        .into_iter()
+        .filter_map(|der| {
+            let cert = der.ok().and_then(|der| parse_der_certificate(&der, store));
+            failed += cert.is_none() as usize;
+            cert
+        })
+        .collect();
+    (certs, failed)
+}
+
+#[cfg(target_os = "linux")]
+fn read_pem_store(
+    name: &str,
+    path: &std::path::Path,
+    trusted_root: bool,
+) -> (CertificateStore, Vec<CertificateInfo>) {
+    let mut store = CertificateStore {
+        name: name.to_string(),
+        path: path.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    let files: Vec<std::path::PathBuf> = if path.is_dir() {
+        std::fs::read_dir(path)
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .map(|e| e.path())
+                    .filter(|p| p.is_file())
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        vec![path.to_path_buf()]
+    };
+    let mut certs = Vec::new();
+    let mut failed = 0;
+    for file in files {
+        match std::fs::read_to_string(&file) {
+            Ok(text) => {
+                let (parsed, bad) = parse_pem_certificates(&text, name);
+                failed += bad;
+                certs.extend(parsed.into_iter().map(|mut cert| {
+                    cert.trusted_root = trusted_root;
+                    cert
+                }));
+            }
+            Err(e) => store.error = Some(format!("{}: {e}", file.display())),
+        }
+    }
+    if failed > 0 && store.error.is_none() {
+        store.error = Some(format!("{failed} certificate(s) could not be parsed"));
+    }
+    store.count = certs.len();
+    (store, certs)
+}
+
+/// `certutil -L` rows: nickname followed by trust flags such as "CT,C,C" or "u,u,u".
+#[cfg(target_os = "linux")]
+fn parse_certutil_list(output: &str) -> Vec<(String, String)> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let line = line.trim_end();
+            let (nickname, trust) = line.rsplit_once(char::is_whitespace)?;
+            let flags: Vec<&str> = trust.split(',').collect();
+            let valid = flags.len() == 3
+                && flags
+                    .iter()
+                    .all(|f| f.chars().all(|c| c.is_ascii_alphabetic()));
+            let nickname = nickname.trim();
+            (valid && !nickname.is_empty() && nickname != "Certificate Nickname")
+                .then(|| (nickname.to_string(), trust.to_string()))
+        })
+        .collect()
+}
+
+/// NSS databases used by Chrome/Chromium and Firefox profiles.
+#[cfg(target_os = "linux")]
+fn nss_databases() -> Vec<(String, std::path::PathBuf)> {
+    let Some(home) = std::env::var_os("HOME").map(std::path::PathBuf::from) else {
+        return Vec::new();
+    };
+    let mut dbs = vec![("NSS (Chrome/Chromium)".to_string(), home.join(".pki/nssdb"))];
+    for firefox in [
+        home.join(".mozilla/firefox"),
+        home.join("snap/firefox/common/.mozilla/firefox"),
+    ] {
+        for entry in std::fs::read_dir(&firefox).into_iter().flatten().flatten() {
+            let profile = entry.path();
+            if profile.join("cert9.db").exists() {
+                dbs.push((
+                    format!("Firefox ({})", entry.file_name().to_string_lossy()),
+                    profile,
+                ));
+            }
+        }
+    }
+    dbs.into_iter()
+        .filter(|(_, dir)| dir.join("cert9.db").exists())
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn read_nss_store(name: &str, dir: &std::path::Path) -> (CertificateStore, Vec<CertificateInfo>) {
+    let db = format!("sql:{}", dir.display());
+    let mut store = CertificateStore {
+        name: name.to_string(),
+        path: dir.to_string_lossy().to_string(),
+        ..Default::default()
+    };
+    let Some((code, listing)) = command_stdout("certutil", &["-L", "-d", &db]) else {
+        store.error =
+            Some("certutil not available (install libnss3-tools / nss-tools)".to_string());
+        return (store, Vec::new());
+    };
+    if code != 0 {
+        store.error = Some("certutil could not open the database".to_string());
+        return (store, Vec::new());
+    }
+
+    let mut certs = Vec::new();
+    for (nickname, trust) in parse_certutil_list(&listing) {
+        let Some((_, pem)) = command_stdout("certutil", &["-L", "-d", &db, "-n", &nickname, "-a"])
+        else {
+            continue;
+        };
+        for mut cert in parse_pem_certificates(&pem, name).0 {
+            let ssl_flags = trust.split(',').next().unwrap_or_default();
+            cert.trusted_root = ssl_flags.contains('C');
+            cert.has_private_key = ssl_flags.contains('u');
+            cert.trust = Some(trust.clone());
+            certs.push(cert);
+        }
+    }
+    store.count = certs.len();
+    (store, certs)
+}
+
+#[cfg(target_os = "linux")]
+fn collect_certificates() -> (
+    Vec<CertificateStore>,
+    Vec<CertificateInfo>,
+    Vec<CertificateInfo>,
+) {
+    let mut stores = Vec::new();
+    let mut all = Vec::new();
+    let mut user = Vec::new();
+
+    if let Some(bundle) = CERT_SYSTEM_BUNDLES
+        .iter()
+        .map(std::path::Path::new)
+        .find(|p| p.exists())
+    {
+        let (store, certs) = read_pem_store("System trust store", bundle, true);
+        stores.push(store);
+        all.extend(certs);
+    }
+    let bundled: Vec<String> = all
+        .iter()
+        .map(|c| normalize_fingerprint(&c.fingerprint_sha256))
+        .collect();
+    for (dir, tool) in CERT_LOCAL_ANCHORS
+        .iter()
+        .map(|(dir, tool)| (std::path::Path::new(dir), tool))
+        .filter(|(dir, _)| dir.is_dir())
+    {
+        // Anchors are only trusted once merged into the system bundle.
+        let (store, mut certs) = read_pem_store("Locally added CAs", dir, false);
+        for cert in certs.iter_mut() {
+            if !bundled.contains(&normalize_fingerprint(&cert.fingerprint_sha256)) {
+                cert.note = Some(format!("Not in the system bundle; pending {tool}"));
+            }
+        }
+        stores.push(store);
+        user.extend(certs.iter().cloned());
+        all.extend(certs);
+    }
+    for (name, dir) in nss_databases() {
+        let (store, certs) = read_nss_store(&name, &dir);
+        stores.push(store);
+        user.extend(certs.iter().cloned());
+        all.extend(certs);
+    }
+    (stores, all, user)
+}
+
+#[cfg(target_os = "windows")]
+const CERT_STORES_SCRIPT: &str = r#"
+$stores = @(
+  @{ Name = 'LocalMachine\Root'; Path = 'Cert:\LocalMachine\Root'; Root = $true },
+  @{ Name = 'LocalMachine\CA'; Path = 'Cert:\LocalMachine\CA'; Root = $false },
+  @{ Name = 'CurrentUser\Root'; Path = 'Cert:\CurrentUser\Root'; Root = $true },
+  @{ Name = 'CurrentUser\My'; Path = 'Cert:\CurrentUser\My'; Root = $false },
+  @{ Name = 'LocalMachine\My'; Path = 'Cert:\LocalMachine\My'; Root = $false }
+)
+$sha = [Security.Cryptography.SHA256]::Create()
+$out = foreach ($s in $stores) {
+  Get-ChildItem -Path $s.Path -ErrorAction SilentlyContinue | ForEach-Object {
+    [pscustomobject]@{
+      store = $s.Name
+      subject = $_.Subject
+      issuer = $_.Issuer
+      common_name = $_.GetNameInfo([Security.Cryptography.X509Certificates.X509NameType]::SimpleName, $false)
+      not_after = $_.NotAfter.ToUniversalTime().ToString('o')
+      fingerprint_sha256 = [BitConverter]::ToString($sha.ComputeHash($_.RawData))
+      trusted_root = $s.Root
+      has_private_key = $_.HasPrivateKey
+    }
+  }
+}
+ConvertTo-Json -InputObject @($out) -Depth 2 -Compress
+"#;
+
+#[cfg(target_os = "windows")]
+fn collect_certificates() -> (
+    Vec<CertificateStore>,
+    Vec<CertificateInfo>,
+    Vec<CertificateInfo>,
+) {
+    let mut certs: Vec<CertificateInfo> = powershell_output(CERT_STORES_SCRIPT)
+        .ok()
+        .and_then(|out| serde_json::from_str(&out).ok())
+        .unwrap_or_default();
+    let mut stores: Vec<CertificateStore> = Vec::new();
+    for cert in certs.iter_mut() {
+        cert.days_left = cert
+            .not_after
+            .as_deref()
+            .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| certificate_days_left(&t.with_timezone(&Utc)));
+        match stores.iter_mut().find(|s| s.name == cert.store) {
+            Some(store) => store.count += 1,
+            None => stores.push(CertificateStore {
+                name: cert.store.clone(),
+                path: format!("Cert:\\{}", cert.store),
+                count: 1,
+                error: None,
+            }),
+        }
+    }
+    let user = certs
+        .iter()
+        .filter(|c| c.store.starts_with("CurrentUser") || c.has_private_key)
+        .cloned()
+        .collect();
+    (stores, certs, user)
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn collect_certificates() -> (
+    Vec<CertificateStore>,
+    Vec<CertificateInfo>,
+    Vec<CertificateInfo>,
+) {
+    (Vec::new(), Vec::new(), Vec::new())
+}
+
+#[tauri::command]
+async fn get_certificate_report(days: Option<u32>) -> Result<CertificateReport, String> {
+    let within_days = days.unwrap_or(CERT_DEFAULT_EXPIRY_DAYS);
+    tauri::async_runtime::spawn_blocking(move || {
+        let (stores, all, user_certificates) = collect_certificates();
+        let mut expiring: Vec<CertificateInfo> = all
+            .iter()
+            .filter(|c| {
+                c.days_left
+                    .map(|d| d <= within_days as i64)
+                    .unwrap_or(false)
+            })
+            .cloned()
+            .collect();
+        expiring.sort_by_key(|c| c.days_left);
+        Ok(CertificateReport {
+            within_days,
+            stores,
+            expiring,
+            user_certificates,
+            required_cas: required_ca_status(&all, &required_cas()),
+            timestamp: Utc::now().to_rfc3339(),
+        })
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+//
+// ───────── Account details ─────────
+//
+
+// Group memberships that grant sudo on common distributions.
+#[cfg(target_os = "linux")]
+const ADMIN_GROUPS: [&str; 3] = ["sudo", "wheel", "admin"];
+
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct AccountDetails {
+    full_name: Option<String>,
+    uid: Option<u32>,
+    // Windows account SID.
+    sid: Option<String>,
+    groups: Vec<String>,
+    is_admin: bool,
+    admin_via: Option<String>,
+    password_last_changed: Option<String>,
+    // Date/RFC 3339 timestamp, or "never".
+    password_expires: Option<String>,
+    password_days_left: Option<i64>,
+    password_expired: bool,
+    account_expires: Option<String>,
+    last_login: Option<String>,
+}
+
+/// Days until an expiry given as a date ("2024-06-01") or RFC 3339 timestamp.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn days_until(expiry: &str) -> Option<i64> {
+    let when = chrono::DateTime::parse_from_rfc3339(expiry)
+        .map(|t| t.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            chrono::NaiveDate::parse_from_str(expiry, "%Y-%m-%d")
+                .ok()
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|t| t.and_utc())
+        })?;
+    Some((when - Utc::now()).num_days())
+}
+
+/// `LC_ALL=C chage -l <user>`, which prints dates as "Jun 01, 2024"; they are
+/// stored as ISO dates.
+#[cfg(target_os = "linux")]
+fn parse_chage(output: &str, details: &mut AccountDetails) {
+    let date = |value: &str| {
+        chrono::NaiveDate::parse_from_str(value, "%b %d, %Y")
+            .map(|d| d.format("%Y-%m-%d").to_string())
+            .ok()
+    };
+    for line in output.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        match key.trim() {
+            "Last password change" => {
+                if value == "password must be changed" {
+                    details.password_expired = true;
+                } else {
+                    details.password_last_changed = date(value);
+                }
+            }
+            "Password expires" => {
+                details.password_expires = match value {
+                    "never" => Some("never".to_string()),
+                    "password must be changed" => {
+                        details.password_expired = true;
+                        None
+                    }
+                    other => date(other),
+                }
+            }
+            "Account expires" => {
+                details.account_expires = if value == "never" {
+                    Some("never".to_string())
+                } else {
+                    date(value)
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// True when `sudo -l` output lets the user run any command as root, e.g.
+/// `(ALL : ALL) ALL` or `(root) NOPASSWD: ALL`.
+#[cfg(target_os = "linux")]
+fn sudo_allows_all(output: &str) -> bool {
+    output
+        .lines()
+        .skip_while(|line| !line.contains("may run the following commands"))
+        .filter_map(|line| line.trim().strip_prefix('('))
+        .filter_map(|rule| rule.split_once(')'))
+        .any(|(runas, commands)| {
+            let user = runas.split(':').next().unwrap_or_default().trim();
+            (user == "ALL" || user == "root")
+                && commands.rsplit(':').next().map(str::trim) == Some("ALL")
+        })
+}
+
+/// Latest login timestamp from `lastlog -u` or `last -F` output.
+#[cfg(target_os = "linux")]
+fn parse_last_login(output: &str) -> Option<String> {
+    if output.contains("Never logged in") {
+        return None;
+    }
+    let re = regex::Regex::new(
+        r"[A-Z][a-z]{2} [A-Z][a-z]{2} +\d{1,2} \d{2}:\d{2}(:\d{2})?( [+-]\d{4})? \d{4}",
+    )
+    .ok()?;
+    output
+        .lines()
+        .find_map(|line| re.find(line))
+        .map(|m| m.as_str().split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+#[cfg(target_os = "linux")]
+fn account_details(username: &str) -> AccountDetails {
+    let mut details = AccountDetails::default();
+
+    // getent also resolves directory (sssd/LDAP) users.
+    if let Some((_, passwd)) = command_stdout("getent", &["passwd", username]) {
+        let cols: Vec<&str> = passwd.trim().split(':').collect();
+        details.uid = cols.get(2).and_then(|u| u.parse().ok());
+        details.full_name = cols
+            .get(4)
+            .and_then(|gecos| gecos.split(',').next())
+            .map(|n| n.trim().to_string())
+            .filter(|n| !n.is_empty());
+    }
+    if let Some((_, groups)) = command_stdout("id", &["-Gn", username]) {
+        details.groups = groups.split_whitespace().map(|g| g.to_string()).collect();
+    }
+    details.admin_via = ADMIN_GROUPS
+        .iter()
+        .find(|g| details.groups.iter().any(|m| m == *g))
+        .map(|g| format!("{g} group"));
+    if details.uid == Some(0) {
+        details.admin_via = Some("root".to_string());
+    }
+    // sudoers rules can also name directory groups or the user directly. -n
+    // never prompts, so this only answers when sudo needs no password.
+    if details.admin_via.is_none() {
+        let mut sudo = Command::new("sudo");
+        sudo.args(["-n", "-l"]).env("LC_ALL", "C");
+        if let Ok(output) = output_with_timeout(sudo, Duration::from_secs(5)) {
+            if output.status.success() && sudo_allows_all(&String::from_utf8_lossy(&output.stdout))
+            {
+                details.admin_via = Some("sudo rules".to_string());
+            }
+        }
+    }
+    details.is_admin = details.admin_via.is_some();
+
+    // Users may list their own aging info; directory accounts have none here.
+    if let Ok(output) = Command::new("chage")
+        .args(["-l", username])
+        .env("LC_ALL", "C")
+        .output()
+    {
+        if output.status.success() {
+            parse_chage(&String::from_utf8_lossy(&output.stdout), &mut details);
+        }
+    }
+    details.password_days_left = details.password_expires.as_deref().and_then(days_until);
+    if details.password_days_left.map(|d| d < 0).unwrap_or(false) {
+        details.password_expired = true;
+    }
+
+    details.last_login = command_stdout("lastlog", &["-u", username])
+        .and_then(|(_, out)| parse_last_login(&out))
+        .or_else(|| {
+            command_stdout("last", &["-F", "-n", "1", username])
+                .and_then(|(_, out)| parse_last_login(&out))
+        });
+    details
+}
+
+#[cfg(target_os = "windows")]
+const ACCOUNT_DETAILS_SCRIPT: &str = r#"
+$id = [Security.Principal.WindowsIdentity]::GetCurrent()
+$groups = @($id.Groups | ForEach-Object { try { $_.Translate([Security.Principal.NTAccount]).Value } catch { $null } } | Where-Object { $_ })
+# UAC filters the token, so check membership by SID rather than IsInRole.
+$isAdmin = [bool]((whoami /groups) -match 'S-1-5-32-544')
+$info = [ordered]@{ sid = $id.User.Value; groups = $groups; is_admin = $isAdmin }
+if ($isAdmin) { $info.admin_via = 'Administrators group' }
+try {
+  Add-Type -AssemblyName System.DirectoryServices.AccountManagement
+  $up = [System.DirectoryServices.AccountManagement.UserPrincipal]::Current
+  $info.full_name = $up.DisplayName
+  if ($up.LastPasswordSet) { $info.password_last_changed = $up.LastPasswordSet.Value.ToUniversalTime().ToString('o') }
+  if ($up.LastLogon) { $info.last_login = $up.LastLogon.Value.ToUniversalTime().ToString('o') }
+  if ($up.AccountExpirationDate) { $info.account_expires = $up.AccountExpirationDate.Value.ToUniversalTime().ToString('o') }
+  if ($up.PasswordNeverExpires) {
+    $info.password_expires = 'never'
+  } elseif ($up.ContextType -eq 'Domain') {
+    $s = New-Object DirectoryServices.DirectorySearcher("(sAMAccountName=$($up.SamAccountName))")
+    $s.PropertiesToLoad.Add('msDS-UserPasswordExpiryTimeComputed') | Out-Null
+    $r = $s.FindOne()
+    if ($r) {
+      $ft = [int64]$r.Properties['msds-userpasswordexpirytimecomputed'][0]
+      if ($ft -eq [int64]::MaxValue) { $info.password_expires = 'never' }
+      elseif ($ft -gt 0) { $info.password_expires = [DateTime]::FromFileTimeUtc($ft).ToString('o') }
+    }
+  } else {
+    $local = Get-LocalUser -Name $up.SamAccountName -ErrorAction SilentlyContinue
+    if ($local -and $local.PasswordExpires) { $info.password_expires = $local.PasswordExpires.ToUniversalTime().ToString('o') }
+  }
+} catch {}
+[pscustomobject]$info | ConvertTo-Json -Depth 3 -Compress
+"#;
+
+#[cfg(target_os = "windows")]
+fn account_details(_username: &str) -> AccountDetails {
+    let mut details: AccountDetails = powershell_output(ACCOUNT_DETAILS_SCRIPT)
+        .ok()
+        .and_then(|out| serde_json::from_str(&out).ok())
+        .unwrap_or_default();
+    details.password_days_left = details.password_expires.as_deref().and_then(days_until);
+    details.password_expired = details.password_days_left.map(|d| d < 0).unwrap_or(false);
+    details
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux")))]
+fn account_details(_username: &str) -> AccountDetails {
+    AccountDetails::default()
+}
+
+//
+// ───────── Directory & Kerberos ─────────
+//
+
+#[cfg(target_os = "linux")]
+const DIRECTORY_DC_PROBE_TIMEOUT: Duration = Duration::from_secs(3);
+// Kerberos TGTs are nudged for renewal this close to expiry.
+#[cfg(target_os = "linux")]
+const KERBEROS_EXPIRY_WARNING_HOURS: i64 = 2;
+
+#[derive(Serialize, Default, Clone)]
+struct RealmInfo {
+    name: String,
+    realm_name: Option<String>,
+    domain_name: Option<String>,
+    configured: bool,
+    server_software: Option<String>,
+    client_software: Option<String>,
+    login_policy: Option<String>,
+    // "realm" or "sssd.conf".
+    source: String,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct KerberosTicket {
+    service: String,
+    valid_from: Option<String>,
+    expires: Option<String>,
+    renew_until: Option<String>,
+    expired: bool,
+}
+
+#[derive(Serialize, Default)]
+struct KerberosCache {
+    cache: Option<String>,
+    default_principal: Option<String>,
+    tickets: Vec<KerberosTicket>,
+    tgt_expires: Option<String>,
+    tgt_renew_until: Option<String>,
+    // `klist -s`: a non-expired ticket is present.
+    valid: bool,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct DomainController {
+    // "ldap" or "kerberos", the SRV service it was found through.
+    service: String,
+    host: String,
+    port: u16,
+    priority: u16,
+    weight: u16,
+    reachable: bool,
+    latency_ms: Option<u64>,
+    error: Option<String>,
+}
+
+#[derive(Serialize, Default)]
+struct DirectoryStatus {
+    joined: bool,
+    domain: Option<String>,
+    realms: Vec<RealmInfo>,
+    sssd_installed: bool,
+    sssd_active: bool,
+    kerberos: Option<KerberosCache>,
+    domain_controllers: Vec<DomainController>,
+    warnings: Vec<String>,
+    timestamp: String,
+}
+
+/// Connects to `host:port`, returning the handshake time in milliseconds.
+fn tcp_probe(host: &str, port: u16, timeout: Duration) -> Result<u64, String> {
+    use std::net::ToSocketAddrs;
+
+    let addrs: Vec<SocketAddr> = (host, port)
+        .to_socket_addrs()
+        .map_err(|e| format!("DNS lookup failed: {e}"))?
+        .collect();
+    let mut last_error = format!("No addresses for {host}");
+    for addr in addrs {
+        let started = std::time::Instant::now();
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(_) => return Ok(started.elapsed().as_millis() as u64),
+            Err(e) => last_error = format!("{addr}: {e}"),
+        }
+    }
+    Err(last_error)
+}
+
+/// `realm list`: an unindented domain line followed by indented `key: value` lines.
+#[cfg(target_os = "linux")]
+fn parse_realm_list(output: &str) -> Vec<RealmInfo> {
+    let mut realms: Vec<RealmInfo> = Vec::new();
+    for line in output.lines() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) {
+            realms.push(RealmInfo {
+                name: line.trim().to_string(),
+                source: "realm".to_string(),
+                ..Default::default()
+            });
+            continue;
+        }
+        let (Some(realm), Some((key, value))) = (realms.last_mut(), line.split_once(':')) else {
+            continue;
+        };
+        let value = Some(value.trim().to_string()).filter(|v| !v.is_empty());
+        match key.trim() {
+            "realm-name" => realm.realm_name = value,
+            "domain-name" => realm.domain_name = value,
+            "configured" => realm.configured = value.as_deref().map(|v| v != "no").unwrap_or(false),
+            "server-software" => realm.server_software = value,
+            "client-software" => realm.client_software = value,
+            "login-policy" => realm.login_policy = value,
+            _ => {}
+        }
+    }
+    realms
+}
+
+/// `[domain/<name>]` sections of sssd.conf (only readable by root on most systems).
+#[cfg(target_os = "linux")]
+fn parse_sssd_conf(content: &str) -> Vec<RealmInfo> {
+    let mut realms: Vec<RealmInfo> = Vec::new();
+    let mut in_domain = false;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_domain = false;
+            if let Some(name) = line
+                .strip_prefix("[domain/")
+                .and_then(|l| l.strip_suffix(']'))
+            {
+                in_domain = true;
+                realms.push(RealmInfo {
+                    name: name.to_string(),
+                    domain_name: Some(name.to_string()),
+                    configured: true,
+                    client_software: Some("sssd".to_string()),
+                    source: "sssd.conf".to_string(),
+                    ..Default::default()
+                });
+            }
+            continue;
+        }
+        let (true, Some(realm), Some((key, value))) =
+            (in_domain, realms.last_mut(), line.split_once('='))
+        else {
+            continue;
+        };
+        let value = value.trim().to_string();
+        match key.trim() {
+            "ad_domain" => realm.domain_name = Some(value),
+            "krb5_realm" => realm.realm_name = Some(value),
+            "id_provider" if value == "ad" => {
+                realm.server_software = Some("active-directory".to_string())
+            }
+            "id_provider" if value == "ipa" => realm.server_software = Some("ipa".to_string()),
+            _ => {}
+        }
+    }
+    realms
+}
+
+/// klist prints local times as MM/DD/YY or MM/DD/YYYY depending on version.
+#[cfg(target_os = "linux")]
+fn parse_klist_time(value: &str) -> Option<chrono::DateTime<Utc>> {
+    ["%m/%d/%Y %H:%M:%S", "%m/%d/%y %H:%M:%S"]
+        .iter()
+        .find_map(|fmt| chrono::NaiveDateTime::parse_from_str(value.trim(), fmt).ok())
+        .and_then(|t| t.and_local_timezone(chrono::Local).earliest())
+        .map(|t| t.with_timezone(&Utc))
+}
+
+/// MIT `klist` output (run with LC_ALL=C).
+#[cfg(target_os = "linux")]
+fn parse_klist(output: &str) -> KerberosCache {
+    let ticket_re =
+        regex::Regex::new(r"^(\d\S+ \d\S+)\s+(\d\S+ \d\S+)\s+(\S+)").expect("static klist regex");
+    let mut cache = KerberosCache::default();
+    let now = Utc::now();
+    for line in output.lines() {
+        let trimmed = line.trim();
+        if let Some(value) = trimmed.strip_prefix("Ticket cache:") {
+            cache.cache = Some(value.trim().to_string());
+        } else if let Some(value) = trimmed.strip_prefix("Default principal:") {
+            cache.default_principal = Some(value.trim().to_string());
+        } else if let Some(value) = trimmed.strip_prefix("renew until") {
+            if let Some(ticket) = cache.tickets.last_mut() {
+                ticket.renew_until = parse_klist_time(value).map(|t| t.to_rfc3339());
+            }
+        } else if let Some(caps) = ticket_re.captures(trimmed) {
+            let expires = parse_klist_time(&caps[2]);
+            cache.tickets.push(KerberosTicket {
+                service: caps[3].to_string(),
+                valid_from: parse_klist_time(&caps[1]).map(|t| t.to_rfc3339()),
+                expires: expires.map(|t| t.to_rfc3339()),
+                renew_until: None,
+                expired: expires.map(|t| t <= now).unwrap_or(false),
+            });
+        }
+    }
+    if let Some(tgt) = cache
+        .tickets
+        .iter()
+        .find(|t| t.service.starts_with("krbtgt/"))
+    {
+        cache.tgt_expires = tgt.expires.clone();
+        cache.tgt_renew_until = tgt.renew_until.clone();
+    }
+    cache
+}
+
+/// SRV answers from `dig +short` ("0 100 389 dc1.example.com.") or
+/// `host -t SRV` ("... has SRV record 0 100 389 dc1.example.com.").
+#[cfg(target_os = "linux")]
+fn parse_srv_records(output: &str, service: &str) -> Vec<DomainController> {
+    let re = regex::Regex::new(r"(\d+) (\d+) (\d+) (\S+?)\.?$").expect("static SRV regex");
+    let mut records: Vec<DomainController> = output
+        .lines()
+        .filter_map(|line| re.captures(line.trim()))
+        .filter_map(|caps| {
+            Some(DomainController {
+                service: service.to_string(),
+                priority: caps[1].parse().ok()?,
+                weight: caps[2].parse().ok()?,
+                port: caps[3].parse().ok()?,
+                host: caps[4].to_string(),
+                ..Default::default()
+            })
+        })
+        .collect();
+    records.sort_by_key(|r| (r.priority, std::cmp::Reverse(r.weight)));
+    records
+}
+
+#[cfg(target_os = "linux")]
+fn lookup_srv(name: &str, service: &str) -> Vec<DomainController> {
+    let attempts: [(&str, Vec<&str>); 2] = [
+        ("dig", vec!["+short", "+time=2", "+tries=1", "SRV", name]),
+        ("host", vec!["-W", "2", "-t", "SRV", name]),
+    ];
+    for (program, args) in attempts {
+        if !command_exists(program) {
+            continue;
+        }
+        let mut command = Command::new(program);
+        command.args(&args);
+        if let Ok(output) = output_with_timeout(command, Duration::from_secs(5)) {
+            let records = parse_srv_records(&String::from_utf8_lossy(&output.stdout), service);
+            if !records.is_empty() {
+                return records;
+            }
+        }
+    }
+    Vec::new()
+}
+
+#[cfg(target_os = "linux")]
+fn directory_realms() -> Vec<RealmInfo> {
+    if command_exists("realm") {
+        let mut command = Command::new("realm");
+        command.arg("list");
+        if let Ok(output) = output_with_timeout(command, Duration::from_secs(10)) {
+            let realms = parse_realm_list(&String::from_utf8_lossy(&output.stdout));
+            if !realms.is_empty() {
+                return realms;
+            }
+        }
+    }
+    std::fs::read_to_string("/etc/sssd/sssd.conf")
+        .map(|c| parse_sssd_conf(&c))
+        .unwrap_or_default()
+}
+
+/// DNS domain of the first configured realm, used for `SystemInfo.domain`.
+/// sssd.conf is read first when it is readable so `realm list` (up to 10 s)
+/// only runs when it has to.
+#[cfg(target_os = "linux")]
+fn linux_directory_domain() -> Option<String> {
+    std::fs::read_to_string("/etc/sssd/sssd.conf")
+        .map(|c| parse_sssd_conf(&c))
+        .ok()
+        .filter(|realms| !realms.is_empty())
+        .unwrap_or_else(directory_realms)
+        .into_iter()
+        .find(|r| r.configured)
+        .map(|r| r.domain_name.unwrap_or(r.name))
+}
+
+#[cfg(target_os = "linux")]
+fn get_directory_status_linux() -> DirectoryStatus {
+    let realms = directory_realms();
+    let domain = realms
+        .iter()
+        .find(|r| r.configured)
+        .map(|r| r.domain_name.clone().unwrap_or_else(|| r.name.clone()));
+    let mut status = DirectoryStatus {
+        joined: domain.is_some(),
+        domain: domain.clone(),
+        realms,
+        sssd_installed: command_exists("sssd") || std::path::Path::new("/usr/sbin/sssd").exists(),
+        sssd_active: systemd_unit_active("sssd"),
+        timestamp: Utc::now().to_rfc3339(),
+        ..Default::default()
+    };
+    if status.joined && !status.sssd_active {
+        status
+            .warnings
+            .push("Machine is joined to a domain but sssd is not running.".to_string());
+    }
+
+    if command_exists("klist") {
+        let valid = Command::new("klist")
+            .arg("-s")
+            .status()
+            .map(|s| s.success())
+            .unwrap_or(false);
+        if let Ok(output) = Command::new("klist").env("LC_ALL", "C").output() {
+            if output.status.success() {
+                let mut cache = parse_klist(&String::from_utf8_lossy(&output.stdout));
+                cache.valid = valid;
+                status.kerberos = Some(cache);
+            }
+        }
+    }
+    match &status.kerberos {
+        Some(cache) if !cache.valid => status
+            .warnings
+            .push("Kerberos ticket has expired; log out and back in or run kinit.".to_string()),
+        Some(cache) => {
+            let expiring = cache
+                .tgt_expires
+                .as_deref()
+                .and_then(|t| chrono::DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&Utc) - Utc::now())
+                .map(|left| left < chrono::Duration::hours(KERBEROS_EXPIRY_WARNING_HOURS))
+                .unwrap_or(false);
+            if expiring {
+                status
+                    .warnings
+                    .push("Kerberos ticket expires within a couple of hours.".to_string());
+            }
+        }
+        None if status.joined => status
+            .warnings
+            .push("No Kerberos ticket cache for this session.".to_string()),
+        None => {}
+    }
+
+    if let Some(domain) = domain {
+        let mut controllers = lookup_srv(&format!("_ldap._tcp.dc._msdcs.{domain}"), "ldap");
+        if controllers.is_empty() {
+            controllers = lookup_srv(&format!("_ldap._tcp.{domain}"), "ldap");
+        }
+        controllers.extend(lookup_srv(&format!("_kerberos._tcp.{domain}"), "kerberos"));
+        let handles: Vec<_> = controllers
+            .into_iter()
+            .map(|mut dc| {
+                std::thread::spawn(move || {
+                    match tcp_probe(&dc.host, dc.port, DIRECTORY_DC_PROBE_TIMEOUT) {
+                        Ok(ms) => {
+                            dc.reachable = true;
+                            dc.latency_ms = Some(ms);
+                        }
+                        Err(e) => dc.error = Some(e),
+                    }
+                    dc
+                })
+            })
+            .collect();
+        status.domain_controllers = handles.into_iter().filter_map(|h| h.join().ok()).collect();
+        if status.domain_controllers.is_empty() {
+            status
+                .warnings
+                .push(format!("No domain controllers found in DNS for {domain}."));
+        } else if !status.domain_controllers.iter().any(|dc| dc.reachable) {
+            status
+                .warnings
+                .push("None of the domain controllers are reachable.".to_string());
+        }
+    }
+    status
+}
+
+#[tauri::command]
+async fn get_directory_status() -> Result<DirectoryStatus, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        #[cfg(target_os = "linux")]
+        {
+            Ok(get_directory_status_linux())
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Err("Directory status is only supported on Linux.".to_string())
+        }
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+//
+// ───────── Network mounts ─────────
+//
+
+#[cfg(target_os = "linux")]
+const MOUNT_STAT_TIMEOUT: Duration = Duration::from_secs(5);
+// A stat slower than this on a live share is reported as "slow".
+#[cfg(target_os = "linux")]
+const MOUNT_SLOW_MS: u64 = 1000;
+// How long a remount may take before it is reported as still pending.
+#[cfg(target_os = "linux")]
+const MOUNT_REMOUNT_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Serialize, Default, Clone)]
+struct NetworkMount {
+    mount_point: String,
+    // cifs, nfs, nfs4, sshfs or davfs.
+    kind: String,
+    fs_type: String,
+    source: String,
+    server: Option<String>,
+    share: Option<String>,
+    options: Vec<String>,
+    // "responsive", "slow", "stale" or "error".
+    state: String,
+    latency_ms: Option<u64>,
+    error: Option<String>,
+    // Managed by systemd (fstab or a .mount unit) and so remountable via D-Bus.
+    systemd_unit: Option<String>,
+    user_fuse: bool,
+}
+
+#[derive(Serialize, Default)]
+struct NetworkMountReport {
+    mounts: Vec<NetworkMount>,
+    stale_count: usize,
+    timestamp: String,
+}
+
+/// Network filesystem kind for a `/proc/mounts` type/source pair.
+#[cfg(target_os = "linux")]
+fn network_mount_kind(fs_type: &str, source: &str) -> Option<&'static str> {
+    match fs_type {
+        "cifs" | "smb3" | "smbfs" => Some("cifs"),
+        "nfs" | "nfs4" => Some("nfs"),
+        "fuse.sshfs" | "sshfs" => Some("sshfs"),
+        "davfs" | "fuse.davfs2" => Some("davfs"),
+        "fuse" if source.starts_with("http://") || source.starts_with("https://") => Some("davfs"),
+        _ => None,
+    }
+}
+
+/// Server and share for `//server/share`, `server:/export`, `user@host:/path`
+/// and `https://host/path` sources.
+#[cfg(target_os = "linux")]
+fn split_mount_source(kind: &str, source: &str) -> (Option<String>, Option<String>) {
+    let non_empty = |s: &str| Some(s.to_string()).filter(|s| !s.is_empty());
+    match kind {
+        "cifs" => {
+            let rest = source.trim_start_matches(['/', '\\']);
+            match rest.split_once(['/', '\\']) {
+                Some((server, share)) => (non_empty(server), non_empty(share)),
+                None => (non_empty(rest), None),
+            }
+        }
+        "davfs" => {
+            let rest = source.split_once("://").map(|(_, r)| r).unwrap_or(source);
+            match rest.split_once('/') {
+                Some((server, path)) => (non_empty(server), non_empty(&format!("/{path}"))),
+                None => (non_empty(rest), None),
+            }
+        }
+        _ => match source.rsplit_once(':') {
+            Some((host, path)) => {
+                let host = host.rsplit('@').next().unwrap_or(host);
+                (
+                    non_empty(host.trim_start_matches('[').trim_end_matches(']')),
+                    non_empty(path),
+                )
+            }
+            None => (None, non_empty(source)),
+        },
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn parse_network_mounts(text: &str) -> Vec<NetworkMount> {
+    text.lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 4 {
+                return None;
+            }
+            let source = unescape_mount_path(fields[0]);
+            let kind = network_mount_kind(fields[2], &source)?;
+            let (server, share) = split_mount_source(kind, &source);
+            Some(NetworkMount {
+                mount_point: unescape_mount_path(fields[1]),
+                kind: kind.to_string(),
+                fs_type: fields[2].to_string(),
+                source,
+                server,
+                share,
+                options: fields[3].split(',').map(|o| o.to_string()).collect(),
+                user_fuse: fields[2].starts_with("fuse"),
+                ..Default::default()
+            })
+        })
+        .collect()
+}
+
+/// Classifies a mount from a `stat` run: timing out or ESTALE means the share
+/// has gone away underneath the mount.
+#[cfg(target_os = "linux")]
+fn classify_mount_probe(result: &Result<(bool, String), String>, elapsed_ms: u64) -> &'static str {
+    match result {
+        Err(_) => "stale",
+        Ok((true, _)) if elapsed_ms >= MOUNT_SLOW_MS => "slow",
+        Ok((true, _)) => "responsive",
+        Ok((false, stderr))
+            if stderr.contains("Stale file handle")
+                || stderr.contains("Transport endpoint is not connected")
+                || stderr.contains("Host is down") =>
+        {
+            "stale"
+        }
+        Ok((false, _)) => "error",
+    }
+}
+
+/// Stats the mount in a child process so a hung share can't block the app.
+#[cfg(target_os = "linux")]
+fn probe_network_mount(mount: &mut NetworkMount) {
+    let mut command = Command::new("stat");
+    command.args(["-t", &mount.mount_point]).env("LC_ALL", "C");
+    let started = std::time::Instant::now();
+    let result = output_with_timeout(command, MOUNT_STAT_TIMEOUT).map(|out| {
+        (
+            out.status.success(),
+            String::from_utf8_lossy(&out.stderr).trim().to_string(),
+        )
+    });
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    mount.state = classify_mount_probe(&result, elapsed_ms).to_string();
+    match result {
+        Ok((true, _)) => mount.latency_ms = Some(elapsed_ms),
+        Ok((false, stderr)) => mount.error = Some(stderr),
+        Err(e) => mount.error = Some(e),
+    }
+}
+
+/// The .mount unit for `mount_point` when it comes from fstab or a unit file.
+/// systemd also loads a unit for every hand-made mount, but those have no
+/// fragment or source path and restarting them only unmounts the share.
+#[cfg(target_os = "linux")]
+fn mount_systemd_unit(mount_point: &str) -> Option<String> {
+    let (_, unit) = command_stdout("systemd-escape", &["--path", "--suffix=mount", mount_point])?;
+    let unit = unit.trim().to_string();
+    let (code, show) = command_stdout(
+        "systemctl",
+        &[
+            "show",
+            "-p",
+            "LoadState",
+            "-p",
+            "FragmentPath",
+            "-p",
+            "SourcePath",
+            &unit,
+        ],
+    )?;
+    let props: std::collections::HashMap<&str, &str> =
+        show.lines().filter_map(|l| l.split_once('=')).collect();
+    let managed = props.get("LoadState") == Some(&"loaded")
+        && ["FragmentPath", "SourcePath"]
+            .iter()
+            .any(|key| props.get(key).map(|v| !v.is_empty()).unwrap_or(false));
+    (code == 0 && managed).then_some(unit)
+}
+
+/// Waits until `unit` has no queued job and returns its ActiveState.
+#[cfg(target_os = "linux")]
+fn wait_for_unit_job(unit: &str, timeout: Duration) -> Option<String> {
+    let started = std::time::Instant::now();
+    loop {
+        let (_, show) = command_stdout(
+            "systemctl",
+            &["show", "-p", "ActiveState", "-p", "Job", unit],
+        )?;
+        let props: std::collections::HashMap<&str, &str> =
+            show.lines().filter_map(|l| l.split_once('=')).collect();
+        if props.get("Job").map(|job| job.is_empty()).unwrap_or(true) {
+            return props.get("ActiveState").map(|state| state.to_string());
+        }
+        if started.elapsed() >= timeout {
+            return None;
+        }
+        sleep(Duration::from_millis(500));
+    }
+}
+
+/// Confirms `mount_point` is mounted again and answers a stat.
+#[cfg(target_os = "linux")]
+fn verify_remount(mount_point: &str) -> Result<String, String> {
+    let text = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+    let mut mount = parse_network_mounts(&text)
+        .into_iter()
+        .find(|m| m.mount_point == mount_point)
+        .ok_or_else(|| format!("{mount_point} is no longer mounted"))?;
+    probe_network_mount(&mut mount);
+    match mount.state.as_str() {
+        "responsive" | "slow" => Ok(format!("{mount_point} remounted")),
+        state => Err(format!("{mount_point} was remounted but is still {state}")),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn list_network_mounts() -> Vec<NetworkMount> {
+    let text = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+    let handles: Vec<_> = parse_network_mounts(&text)
+        .into_iter()
+        .map(|mut mount| {
+            std::thread::spawn(move || {
+                probe_network_mount(&mut mount);
+                mount.systemd_unit = mount_systemd_unit(&mount.mount_point);
+                mount
+            })
+        })
+        .collect();
+    handles.into_iter().filter_map(|h| h.join().ok()).collect()
+}
+
+#[tauri::command]
+async fn get_network_mounts() -> Result<NetworkMountReport, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        #[cfg(target_os = "linux")]
+        {
+            let mounts = list_network_mounts();
+            Ok(NetworkMountReport {
+                stale_count: mounts.iter().filter(|m| m.state == "stale").count(),
+                mounts,
+                timestamp: Utc::now().to_rfc3339(),
+            })
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            Err("Network mount health is only supported on Linux.".to_string())
+        }
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+/// Remounts a network share. systemd-managed mounts are restarted over D-Bus
+/// (polkit may prompt); user FUSE mounts are lazily detached and remounted
+/// from fstab when they have an entry there.
+#[tauri::command]
+async fn remount_network_mount(mount_point: String) -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        #[cfg(target_os = "linux")]
+        {
+            let text = std::fs::read_to_string("/proc/mounts").unwrap_or_default();
+            let mount = parse_network_mounts(&text)
+                .into_iter()
+                .find(|m| m.mount_point == mount_point)
+                .ok_or_else(|| format!("{mount_point} is not a mounted network share"))?;
+
+            if !mount.user_fuse {
+                let unit = mount_systemd_unit(&mount.mount_point).ok_or_else(|| {
+                    format!("{mount_point} is not managed by systemd and needs an administrator to remount it")
+                })?;
+                restart_unit_dbus(&unit, "system")?;
+                return match wait_for_unit_job(&unit, MOUNT_REMOUNT_TIMEOUT) {
+                    None => Err(format!("Remount of {mount_point} is still pending")),
+                    Some(state) if state != "active" => {
+                        Err(format!("Remount of {mount_point} failed ({unit} is {state})"))
+                    }
+                    Some(_) => verify_remount(&mount_point),
+                };
+            }
+
+            // A lazy unmount of a hung share can still block on the FUSE daemon.
+            let mut detach = Command::new("fusermount");
+            detach.args(["-uz", &mount.mount_point]);
+            let detached = output_with_timeout(detach, MOUNT_STAT_TIMEOUT)
+                .map_err(|e| format!("Failed to run fusermount: {e}"))?;
+            if !detached.status.success() {
+                return Err(format!("Failed to detach {mount_point}"));
+            }
+            let mut command = Command::new("mount");
+            command.arg(&mount.mount_point);
+            match output_with_timeout(command, MOUNT_REMOUNT_TIMEOUT) {
+                Ok(out) if out.status.success() => verify_remount(&mount_point),
+                _ => Ok(format!(
+                    "{mount_point} was detached; reconnect it from the app that mounted it"
+                )),
+            }
+        }
+        #[cfg(not(target_os = "linux"))]
+        {
+            let _ = mount_point;
+            Err("Network mount health is only supported on Linux.".to_string())
+        }
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+//
+// ───────── Browser diagnostics ─────────
+//
+
+// Cache size above which a browser is flagged as a likely culprit.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+const BROWSER_LARGE_CACHE_BYTES: u64 = 2 * 1024 * 1024 * 1024;
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+struct BrowserDef {
+    name: &'static str,
+    // "chromium" or "firefox" profile layout.
+    engine: &'static str,
+    #[cfg(target_os = "linux")]
+    linux: LinuxBrowserPaths,
+    #[cfg(target_os = "windows")]
+    windows: WindowsBrowserPaths,
+}
+
+#[cfg(target_os = "linux")]
+struct LinuxBrowserPaths {
+    // Profile roots and cache root, relative to $HOME.
+    profile_roots: &'static [&'static str],
+    cache_root: &'static str,
+    binaries: &'static [&'static str],
+    // Managed policy directories or files.
+    policies: &'static [&'static str],
+}
+
+#[cfg(target_os = "windows")]
+struct WindowsBrowserPaths {
+    // Profile root relative to %LOCALAPPDATA% (Chromium) or %APPDATA% (Firefox).
+    profile_root: &'static str,
+    executables: &'static [&'static str],
+    policy_key: &'static str,
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+const BROWSERS: [BrowserDef; 4] = [
+    BrowserDef {
+        name: "Chrome",
+        engine: "chromium",
+        #[cfg(target_os = "linux")]
+        linux: LinuxBrowserPaths {
+            profile_roots: &[".config/google-chrome"],
+            cache_root: ".cache/google-chrome",
+            binaries: &["google-chrome", "google-chrome-stable"],
+            policies: &["/etc/opt/chrome/policies/managed"],
+        },
+        #[cfg(target_os = "windows")]
+        windows: WindowsBrowserPaths {
+            profile_root: "Google\\Chrome\\User Data",
+            executables: &[
+                "C:\\Program Files\\Google\\Chrome\\Application\\chrome.exe",
+                "C:\\Program Files (x86)\\Google\\Chrome\\Application\\chrome.exe",
+            ],
+            policy_key: "SOFTWARE\\Policies\\Google\\Chrome",
+        },
+    },
+    BrowserDef {
+        name: "Edge",
+        engine: "chromium",
+        #[cfg(target_os = "linux")]
+        linux: LinuxBrowserPaths {
+            profile_roots: &[".config/microsoft-edge"],
+            cache_root: ".cache/microsoft-edge",
+            binaries: &["microsoft-edge", "microsoft-edge-stable"],
+            policies: &["/etc/opt/edge/policies/managed"],
+        },
+        #[cfg(target_os = "windows")]
+        windows: WindowsBrowserPaths {
+            profile_root: "Microsoft\\Edge\\User Data",
+            executables: &[
+                "C:\\Program Files (x86)\\Microsoft\\Edge\\Application\\msedge.exe",
+                "C:\\Program Files\\Microsoft\\Edge\\Application\\msedge.exe",
+            ],
+            policy_key: "SOFTWARE\\Policies\\Microsoft\\Edge",
+        },
+    },
+    BrowserDef {
+        name: "Brave",
+        engine: "chromium",
+        #[cfg(target_os = "linux")]
+        linux: LinuxBrowserPaths {
+            profile_roots: &[".config/BraveSoftware/Brave-Browser"],
+            cache_root: ".cache/BraveSoftware/Brave-Browser",
+            binaries: &["brave-browser", "brave"],
+            policies: &["/etc/brave/policies/managed"],
+        },
+        #[cfg(target_os = "windows")]
+        windows: WindowsBrowserPaths {
+            profile_root: "BraveSoftware\\Brave-Browser\\User Data",
+            executables: &[
+                "C:\\Program Files\\BraveSoftware\\Brave-Browser\\Application\\brave.exe",
+            ],
+            policy_key: "SOFTWARE\\Policies\\BraveSoftware\\Brave",
+        },
+    },
+    BrowserDef {
+        name: "Firefox",
+        engine: "firefox",
+        #[cfg(target_os = "linux")]
+        linux: LinuxBrowserPaths {
+            profile_roots: &[".mozilla/firefox", "snap/firefox/common/.mozilla/firefox"],
+            cache_root: ".cache/mozilla/firefox",
+            binaries: &["firefox", "firefox-esr"],
+            policies: &[
+                "/etc/firefox/policies/policies.json",
+                "/usr/lib/firefox/distribution/policies.json",
+                "/usr/lib64/firefox/distribution/policies.json",
+            ],
+        },
+        #[cfg(target_os = "windows")]
+        windows: WindowsBrowserPaths {
+            profile_root: "Mozilla\\Firefox",
+            executables: &[
+                "C:\\Program Files\\Mozilla Firefox\\firefox.exe",
+                "C:\\Program Files (x86)\\Mozilla Firefox\\firefox.exe",
+            ],
+            policy_key: "SOFTWARE\\Policies\\Mozilla\\Firefox",
+        },
+    },
+];
+
+// Chromium keeps these per-profile; on Linux they live under ~/.cache instead.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+const CHROMIUM_CACHE_DIRS: [&str; 3] = ["Cache", "Code Cache", "GPUCache"];
+
+#[derive(Serialize, Default, Clone)]
+struct BrowserExtension {
+    id: String,
+    name: String,
+    version: Option<String>,
+    enabled: bool,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct BrowserProfile {
+    name: String,
+    path: String,
+    profile_bytes: u64,
+    cache_bytes: u64,
+    extensions: Vec<BrowserExtension>,
+}
+
+#[derive(Serialize, Default)]
+struct BrowserInfo {
+    name: String,
+    installed: bool,
+    version: Option<String>,
+    executable: Option<String>,
+    profiles: Vec<BrowserProfile>,
+    managed_policy: bool,
+    // Policy files or registry keys that were found.
+    policy_sources: Vec<String>,
+    warnings: Vec<String>,
+}
+
+#[derive(Serialize, Default)]
+struct BrowserDiagnostics {
+    browsers: Vec<BrowserInfo>,
+    timestamp: String,
+}
+
+/// Total size of the files under `path`, without following symlinks.
+fn directory_size(path: &std::path::Path) -> u64 {
+    let Ok(meta) = std::fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !meta.is_dir() {
+        return meta.len();
+    }
+    std::fs::read_dir(path)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| directory_size(&entry.path()))
+        .sum()
+}
+
+/// Resolves `__MSG_name__` placeholders against the extension's default locale.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn chromium_extension_name(version_dir: &std::path::Path, manifest: &serde_json::Value) -> String {
+    let raw = manifest
+        .get("name")
+        .and_then(|n| n.as_str())
+        .unwrap_or_default();
+    let Some(key) = raw
+        .strip_prefix("__MSG_")
+        .and_then(|k| k.strip_suffix("__"))
+    else {
+        return raw.to_string();
+    };
+    let locale = manifest
+        .get("default_locale")
+        .and_then(|l| l.as_str())
+        .unwrap_or("en");
+    std::fs::read_to_string(
+        version_dir
+            .join("_locales")
+            .join(locale)
+            .join("messages.json"),
+    )
+    .ok()
+    .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+    .and_then(|messages| {
+        messages.as_object().and_then(|map| {
+            map.iter()
+                .find(|(k, _)| k.eq_ignore_ascii_case(key))
+                .and_then(|(_, v)| v.get("message")?.as_str().map(|s| s.to_string()))
+        })
+    })
+    .unwrap_or_else(|| raw.to_string())
+}
+
+/// Installed extensions from `<profile>/Extensions/<id>/<version>/manifest.json`,
+/// with enabled state taken from the profile's preferences.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn chromium_extensions(profile: &std::path::Path) -> Vec<BrowserExtension> {
+    let settings: serde_json::Value = ["Secure Preferences", "Preferences"]
+        .iter()
+        .filter_map(|f| std::fs::read_to_string(profile.join(f)).ok())
+        .filter_map(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+        .find_map(|prefs| prefs.pointer("/extensions/settings").cloned())
+        .unwrap_or_default();
+
+    let mut extensions = Vec::new();
+    for entry in std::fs::read_dir(profile.join("Extensions"))
+        .into_iter()
+        .flatten()
+        .flatten()
+    {
+        let id = entry.file_name().to_string_lossy().to_string();
+        let Some(version_dir) = std::fs::read_dir(entry.path())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|v| v.path())
+            .filter(|v| v.join("manifest.json").is_file())
+            .max()
+        else {
+            continue;
+        };
+        let Some(manifest) = std::fs::read_to_string(version_dir.join("manifest.json"))
+            .ok()
+            .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+        else {
+            continue;
+        };
+        let setting = settings.get(&id);
+        let disabled = setting
+            .and_then(|s| s.get("disable_reasons"))
+            .map(|r| {
+                r.as_array()
+                    .map(|a| !a.is_empty())
+                    .unwrap_or(r.as_u64() != Some(0))
+            })
+            .unwrap_or(false)
+            || setting
+                .and_then(|s| s.get("state"))
+                .and_then(|s| s.as_u64())
+                == Some(0);
+        extensions.push(BrowserExtension {
+            name: chromium_extension_name(&version_dir, &manifest),
+            version: manifest
+                .get("version")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string()),
+            enabled: !disabled,
+            id,
+        });
+    }
+    extensions.sort_by_key(|e| e.name.to_lowercase());
+    extensions
+}
+
+/// Chromium profiles are `Default` and `Profile N` directories holding a Preferences file.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn chromium_profiles(
+    user_data: &std::path::Path,
+    cache_root: Option<&std::path::Path>,
+) -> Vec<BrowserProfile> {
+    let mut profiles = Vec::new();
+    for entry in std::fs::read_dir(user_data).into_iter().flatten().flatten() {
+        let path = entry.path();
+        let dir_name = entry.file_name().to_string_lossy().to_string();
+        if !path.join("Preferences").is_file() || dir_name == "System Profile" {
+            continue;
+        }
+        let name = std::fs::read_to_string(path.join("Preferences"))
+            .ok()
+            .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+            .and_then(|prefs| {
+                prefs
+                    .pointer("/profile/name")
+                    .and_then(|n| n.as_str())
+                    .map(|n| n.to_string())
+            })
+            .unwrap_or_else(|| dir_name.clone());
+        let mut cache_bytes: u64 = CHROMIUM_CACHE_DIRS
+            .iter()
+            .map(|d| directory_size(&path.join(d)))
+            .sum();
+        if let Some(cache_root) = cache_root {
+            cache_bytes += directory_size(&cache_root.join(&dir_name));
+        }
+        profiles.push(BrowserProfile {
+            name,
+            path: path.to_string_lossy().to_string(),
+            profile_bytes: directory_size(&path),
+            cache_bytes,
+            extensions: chromium_extensions(&path),
+        });
+    }
+    profiles
+}
+
+/// Chromium writes its version to `<user data>/Last Version`.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn chromium_profile_version(user_data: &std::path::Path) -> Option<String> {
+    std::fs::read_to_string(user_data.join("Last Version"))
+        .ok()
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+}
+
+/// `[ProfileN]` sections of Firefox's profiles.ini as (name, path).
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn parse_firefox_profiles_ini(
+    text: &str,
+    root: &std::path::Path,
+) -> Vec<(String, std::path::PathBuf)> {
+    let mut profiles = Vec::new();
+    let mut current: Option<(Option<String>, Option<String>, bool)> = None;
+    let mut flush = |entry: Option<(Option<String>, Option<String>, bool)>| {
+        if let Some((name, Some(path), relative)) = entry {
+            let full = if relative {
+                root.join(&path)
+            } else {
+                std::path::PathBuf::from(&path)
+            };
+            profiles.push((name.unwrap_or(path), full));
+        }
+    };
+    for line in text.lines().map(str::trim) {
+        if line.starts_with('[') {
+            flush(current.take());
+            if line.starts_with("[Profile") {
+                current = Some((None, None, true));
+            }
+            continue;
+        }
+        let (Some(entry), Some((key, value))) = (current.as_mut(), line.split_once('=')) else {
+            continue;
+        };
+        match key {
+            "Name" => entry.0 = Some(value.to_string()),
+            "Path" => entry.1 = Some(value.to_string()),
+            "IsRelative" => entry.2 = value != "0",
+            _ => {}
+        }
+    }
+    flush(current);
+    profiles
+}
+
+/// User-installed add-ons from a Firefox profile's extensions.json.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn parse_firefox_extensions(text: &str) -> Vec<BrowserExtension> {
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(text) else {
+        return Vec::new();
+    };
+    let mut extensions: Vec<BrowserExtension> = json
+        .get("addons")
+        .and_then(|a| a.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|a| a.get("type").and_then(|t| t.as_str()) == Some("extension"))
+        .filter(|a| {
+            !matches!(
+                a.get("location").and_then(|l| l.as_str()),
+                Some("app-builtin") | Some("app-system-defaults") | Some("app-system-addons")
+            )
+        })
+        .map(|a| BrowserExtension {
+            id: a
+                .get("id")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            name: a
+                .pointer("/defaultLocale/name")
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+            version: a
+                .get("version")
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string()),
+            enabled: a.get("active").and_then(|v| v.as_bool()).unwrap_or(false),
+        })
+        .collect();
+    extensions.sort_by_key(|e| e.name.to_lowercase());
+    extensions
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn firefox_profiles(
+    root: &std::path::Path,
+    cache_root: Option<&std::path::Path>,
+) -> Vec<BrowserProfile> {
+    let ini = std::fs::read_to_string(root.join("profiles.ini")).unwrap_or_default();
+    parse_firefox_profiles_ini(&ini, root)
+        .into_iter()
+        .filter(|(_, path)| path.is_dir())
+        .map(|(name, path)| {
+            let dir_name = path.file_name().map(|n| n.to_owned()).unwrap_or_default();
+            let mut cache_bytes = directory_size(&path.join("cache2"));
+            if let Some(cache_root) = cache_root {
+                cache_bytes += directory_size(&cache_root.join(&dir_name).join("cache2"));
+            }
+            BrowserProfile {
+                name,
+                profile_bytes: directory_size(&path),
+                cache_bytes,
+                extensions: std::fs::read_to_string(path.join("extensions.json"))
+                    .map(|text| parse_firefox_extensions(&text))
+                    .unwrap_or_default(),
+                path: path.to_string_lossy().to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Firefox records `LastVersion=121.0_20231211174248/...` in compatibility.ini.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn firefox_profile_version(profiles: &[BrowserProfile]) -> Option<String> {
+    profiles.iter().find_map(|p| {
+        let text = std::fs::read_to_string(std::path::Path::new(&p.path).join("compatibility.ini"))
+            .ok()?;
+        text.lines()
+            .find_map(|l| l.strip_prefix("LastVersion="))
+            .and_then(|v| v.split('_').next())
+            .map(|v| v.to_string())
+    })
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn collect_browser_profiles(
+    def: &BrowserDef,
+    roots: &[std::path::PathBuf],
+    cache_root: Option<&std::path::Path>,
+) -> (Vec<BrowserProfile>, Option<String>) {
+    let mut profiles = Vec::new();
+    let mut version = None;
+    for root in roots.iter().filter(|r| r.is_dir()) {
+        if def.engine == "firefox" {
+            profiles.extend(firefox_profiles(root, cache_root));
+        } else {
+            profiles.extend(chromium_profiles(root, cache_root));
+            version = version.or_else(|| chromium_profile_version(root));
+        }
+    }
+    if def.engine == "firefox" {
+        version = firefox_profile_version(&profiles);
+    }
+    (profiles, version)
+}
+
+#[cfg(target_os = "linux")]
+fn inspect_browser(def: &BrowserDef) -> BrowserInfo {
+    let home = std::env::var_os("HOME")
+        .map(std::path::PathBuf::from)
+        .unwrap_or_default();
+    let roots: Vec<std::path::PathBuf> = def
+        .linux
+        .profile_roots
+        .iter()
+        .map(|r| home.join(r))
+        .collect();
+    let cache_root = home.join(def.linux.cache_root);
+    let (profiles, profile_version) = collect_browser_profiles(def, &roots, Some(&cache_root));
+
+    let executable = def
+        .linux
+        .binaries
+        .iter()
+        .find(|b| command_exists(b))
+        .map(|b| b.to_string());
+    // `--version` prints e.g. "Google Chrome 120.0.6099.109" without opening a window.
+    let version = profile_version.or_else(|| {
+        let binary = executable.as_deref()?;
+        let (_, out) = command_stdout(binary, &["--version"])?;
+        regex::Regex::new(r"\d+(\.\d+)+")
+            .ok()?
+            .find(&out)
+            .map(|m| m.as_str().to_string())
+    });
+    let policy_sources: Vec<String> = def
+        .linux
+        .policies
+        .iter()
+        .filter(|p| {
+            let path = std::path::Path::new(p);
+            if path.is_dir() {
+                std::fs::read_dir(path)
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .any(|e| e.path().extension().map(|x| x == "json").unwrap_or(false))
+            } else {
+                path.is_file()
+            }
+        })
+        .map(|p| p.to_string())
+        .collect();
+
+    BrowserInfo {
+        name: def.name.to_string(),
+        installed: executable.is_some() || !profiles.is_empty(),
+        version,
+        executable,
+        profiles,
+        managed_policy: !policy_sources.is_empty(),
+        policy_sources,
+        warnings: Vec::new(),
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn inspect_browser(def: &BrowserDef) -> BrowserInfo {
+    let base = if def.engine == "firefox" {
+        env::var("APPDATA")
+    } else {
+        env::var("LOCALAPPDATA")
+    }
+    .map(PathBuf::from)
+    .unwrap_or_default();
+    let roots = vec![base.join(def.windows.profile_root)];
+    // Firefox keeps its cache under LOCALAPPDATA, mirroring the profile layout.
+    let cache_root = (def.engine == "firefox")
+        .then(|| env::var("LOCALAPPDATA").ok())
+        .flatten()
+        .map(|l| PathBuf::from(l).join("Mozilla\\Firefox\\Profiles"));
+    let (profiles, profile_version) = collect_browser_profiles(def, &roots, cache_root.as_deref());
+
+    let executable = def
+        .windows
+        .executables
+        .iter()
+        .find(|p| Path::new(p).is_file())
+        .map(|p| p.to_string());
+    let version = profile_version.or_else(|| {
+        let exe = executable.as_deref()?;
+        run_powershell_text(&format!(
+            "(Get-Item '{}').VersionInfo.ProductVersion",
+            exe.replace('\'', "''")
+        ))
+        .ok()
+        .filter(|v| !v.is_empty())
+    });
+    let policy_sources: Vec<String> = ["HKLM", "HKCU"]
+        .iter()
+        .map(|hive| format!("{hive}:\\{}", def.windows.policy_key))
+        .filter(|key| {
+            run_powershell_text(&format!(
+                "$k = Get-Item '{key}' -ErrorAction SilentlyContinue; if ($k -and ($k.ValueCount -gt 0 -or $k.SubKeyCount -gt 0)) {{ 'yes' }}"
+            ))
+            .map(|out| out == "yes")
+            .unwrap_or(false)
+        })
+        .collect();
+
+    BrowserInfo {
+        name: def.name.to_string(),
+        installed: executable.is_some() || !profiles.is_empty(),
+        version,
+        executable,
+        profiles,
+        managed_policy: !policy_sources.is_empty(),
+        policy_sources,
+        warnings: Vec::new(),
+    }
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+fn browser_warnings(info: &BrowserInfo) -> Vec<String> {
+    let mut warnings = Vec::new();
+    for profile in &info.profiles {
+        if profile.cache_bytes >= BROWSER_LARGE_CACHE_BYTES {
+            warnings.push(format!(
+                "{} profile \"{}\" has a {:.1} GB cache; clearing it may help.",
+                info.name,
+                profile.name,
+                bytes_to_gb(profile.cache_bytes)
+            ));
+        }
+        let disabled = profile.extensions.iter().filter(|e| !e.enabled).count();
+        if disabled > 0 {
+            warnings.push(format!(
+                "{} profile \"{}\" has {disabled} disabled extension(s).",
+                info.name, profile.name
+            ));
+        }
+    }
+    warnings
+}
+
+#[tauri::command]
+async fn get_browser_diagnostics() -> Result<BrowserDiagnostics, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        #[cfg(any(target_os = "windows", target_os = "linux"))]
+        {
+            let browsers = BROWSERS
+                .iter()
+                .map(|def| {
+                    let mut info = inspect_browser(def);
+                    info.warnings = browser_warnings(&info);
+                    info
+                })
+                .filter(|info| info.installed)
+                .collect();
+            Ok(BrowserDiagnostics {
+                browsers,
+                timestamp: Utc::now().to_rfc3339(),
+            })
+        }
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+        {
+            Err("Browser diagnostics are not supported on this OS.".to_string())
+        }
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+//
+// ───────── App context collectors ─────────
+//
+
+// Shipped collectors; a remotely refreshed copy in app-local data takes precedence.
+const APP_COLLECTORS_DEFAULT: &str = include_str!("../resources/app-collectors.json");
+const APP_COLLECTORS_FILE: &str = "app-collectors.json";
+const APP_COLLECTORS_URL_ENV: &str = "GOLPAC_APP_COLLECTORS_URL";
+const APP_COLLECTOR_COMMAND_TIMEOUT: Duration = Duration::from_secs(5);
+
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct AppCollectorCatalog {
+    version: u32,
+    collectors: Vec<AppCollector>,
+}
+
+/// Declares what to read for one application; everything found is reported
+/// as label/value pairs in `AppContextInfo.details`.
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct AppCollector {
+    name: String,
+    // Lower-case ticket categories this collector answers for.
+    aliases: Vec<String>,
+    // "windows", "linux" or "macos"; empty means every platform.
+    platforms: Vec<String>,
+    registry: Vec<RegistryCollector>,
+    files: Vec<FileCollector>,
+    commands: Vec<CommandCollector>,
+    processes: Vec<String>,
+    // Pass/fail checks reported by `get_app_health`.
+    health: Vec<HealthProbe>,
+}
+
+/// A reported value: the first of `names` that yields anything wins.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+struct CollectorValue {
+    label: String,
+    names: Vec<String>,
+}
+
+/// Values of the first existing key in `keys`, or of the first subkey (searched
+/// recursively) whose name matches `subkey_match`. The name `(key)` yields the
+/// key's own name.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+struct RegistryCollector {
+    keys: Vec<String>,
+    subkey_match: Option<String>,
+    values: Vec<CollectorValue>,
+}
+
+/// Files matched by `paths` (`~` and `*` supported). `format` is "ini"
+/// (`Section.Key` names), "json" (JSON pointers), "prefs" (Mozilla prefs.js,
+/// `*` allowed in names), "text" (first line), "size" or "exists"; the last
+/// three report under `label`.
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+struct FileCollector {
+    label: Option<String>,
+    paths: Vec<String>,
+    format: String,
+    values: Vec<CollectorValue>,
+}
+
+/// Command output, optionally narrowed by a regex (first capture group wins).
+#[derive(Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+struct CommandCollector {
+    label: String,
+    program: String,
+    args: Vec<String>,
+    pattern: Option<String>,
+}
+
+impl AppCollectorCatalog {
+    fn for_platform<'a>(&'a self, platform: &'a str) -> impl Iterator<Item = &'a AppCollector> {
+        self.collectors.iter().filter(move |c| {
+            c.platforms.is_empty() || c.platforms.iter().any(|p| p.eq_ignore_ascii_case(platform))
+        })
+    }
+
+    fn matching<'a>(
+        &'a self,
+        category: &str,
+        platform: &'a str,
+    ) -> impl Iterator<Item = &'a AppCollector> {
+        let needle = category.trim().to_lowercase();
+        self.for_platform(platform).filter(move |c| {
+            !needle.is_empty()
+                && (c.name.to_lowercase() == needle
+                    || c.aliases.iter().any(|a| a.to_lowercase() == needle))
+        })
+    }
+}
+
+fn parse_app_collectors(text: &str) -> Result<AppCollectorCatalog, String> {
+    let catalog: AppCollectorCatalog =
+        serde_json::from_str(text).map_err(|e| format!("Invalid app collector catalog: {e}"))?;
+    if catalog.collectors.is_empty() {
+        return Err("App collector catalog has no collectors".to_string());
+    }
+    if catalog.collectors.iter().any(|c| c.name.trim().is_empty()) {
+        return Err("App collector without a name".to_string());
+    }
+    let patterns = catalog.collectors.iter().flat_map(|c| {
+        c.commands
+            .iter()
+            .filter_map(|cmd| cmd.pattern.as_deref())
+            .chain(c.registry.iter().filter_map(|r| r.subkey_match.as_deref()))
+    });
+    for pattern in patterns {
+        regex::Regex::new(pattern)
+            .map_err(|e| format!("Invalid collector pattern {pattern}: {e}"))?;
+    }
+    Ok(catalog)
+}
+
+/// `*` matches any run of characters, everything else literally.
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or_default();
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    for (i, part) in parts.iter().enumerate() {
+        if i == parts.len() - 1 {
+            return rest.ends_with(part);
+        }
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    rest.is_empty()
+}
+
+/// Expands `~`, `%VAR%` and `*` path components into existing paths.
+fn expand_collector_path(pattern: &str) -> Vec<std::path::PathBuf> {
+    let mut expanded = pattern.to_string();
+    if let Some(rest) = expanded.strip_prefix("~/") {
+        let home = std::env::var("HOME")
+            .or_else(|_| std::env::var("USERPROFILE"))
+            .unwrap_or_default();
+        expanded = format!("{home}/{rest}");
+    }
+    let var_re = regex::Regex::new(r"%([A-Za-z0-9_]+)%").expect("static env regex");
+    let expanded = var_re
+        .replace_all(&expanded, |caps: &regex::Captures| {
+            std::env::var(&caps[1]).unwrap_or_default()
+        })
+        .to_string();
+
+    let mut paths = vec![std::path::PathBuf::new()];
+    for component in std::path::Path::new(&expanded).components() {
+        let part = component.as_os_str().to_string_lossy().to_string();
+        if !part.contains('*') {
+            paths.iter_mut().for_each(|p| p.push(component));
+            continue;
+        }
+        paths = paths
+            .iter()
+            .flat_map(|dir| std::fs::read_dir(dir).into_iter().flatten().flatten())
+            .filter(|entry| wildcard_match(&part, &entry.file_name().to_string_lossy()))
+            .map(|entry| entry.path())
+            .collect();
+        paths.sort();
+    }
+    paths.retain(|p| p.exists());
+    paths
+}
+
+/// `user_pref("key", value);` lines from a Mozilla prefs.js.
+fn parse_prefs_js(text: &str) -> Vec<(String, String)> {
+    let re =
+        regex::Regex::new(r#"^user_pref\("([^"]+)",\s*(.*)\);\s*$"#).expect("static prefs regex");
+    text.lines()
+        .filter_map(|line| re.captures(line.trim()))
+        .map(|caps| {
+            (
+                caps[1].to_string(),
+                caps[2].trim().trim_matches('"').to_string(),
+            )
+        })
+        .collect()
+}
+
+/// Key-file/INI pairs keyed as `Section.Key`.
+fn parse_ini_pairs(text: &str) -> Vec<(String, String)> {
+    let mut section = String::new();
+    let mut pairs = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            section = name.to_string();
+        } else if let Some((key, value)) = line.split_once('=') {
+            pairs.push((
+                format!("{section}.{}", key.trim()),
+                value.trim().to_string(),
+            ));
+        }
+    }
+    pairs
+}
+
+fn json_scalar(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::Null => None,
+        serde_json::Value::String(s) => Some(s.clone()),
+        other => Some(other.to_string()),
+    }
+}
+
+/// Resolves each value spec against `files` in the given format.
+fn collect_file_values(
+    collector: &FileCollector,
+    files: &[std::path::PathBuf],
+    out: &mut serde_json::Map<String, serde_json::Value>,
+) {
+    let label = collector
+        .label
+        .clone()
+        .unwrap_or_else(|| "Files".to_string());
+    match collector.format.as_str() {
+        "exists" => {
+            if !files.is_empty() {
+                out.insert(label, "yes".into());
+            }
+            return;
+        }
+        "size" => {
+            if !files.is_empty() {
+                let bytes: u64 = files.iter().map(|f| directory_size(f)).sum();
+                out.insert(label, format!("{:.2} GB", bytes_to_gb(bytes)).into());
+            }
+            return;
+        }
+        "text" => {
+            if let Some(line) = files
+                .iter()
+                .filter_map(|f| std::fs::read_to_string(f).ok())
+                .find_map(|t| t.lines().next().map(|l| l.trim().to_string()))
+            {
+                out.insert(label, line.into());
+            }
+            return;
+        }
+        _ => {}
+    }
+
+    let documents: Vec<String> = files
+        .iter()
+        .filter(|f| f.is_file())
+        .filter_map(|f| std::fs::read_to_string(f).ok())
+        .collect();
+    for spec in &collector.values {
+        let found = spec.names.iter().find_map(|name| {
+            let mut values: Vec<String> = Vec::new();
+            for doc in &documents {
+                let matches: Vec<String> = match collector.format.as_str() {
+                    "json" => serde_json::from_str::<serde_json::Value>(doc)
+                        .ok()
+                        .and_then(|json| json.pointer(name).and_then(json_scalar))
+                        .into_iter()
+                        .collect(),
+                    "prefs" => parse_prefs_js(doc)
+                        .into_iter()
+                        .filter(|(k, _)| wildcard_match(name, k))
+                        .map(|(_, v)| v)
+                        .collect(),
+                    _ => parse_ini_pairs(doc)
+                        .into_iter()
+                        .filter(|(k, _)| wildcard_match(name, k))
+                        .map(|(_, v)| v)
+                        .collect(),
+                };
+                for value in matches {
+                    if !value.is_empty() && !values.contains(&value) {
+                        values.push(value);
+                    }
+                }
+            }
+            (!values.is_empty()).then(|| values.join(", "))
+        });
+        if let Some(value) = found {
+            out.insert(spec.label.clone(), value.into());
+        }
+    }
+}
+
+fn run_collector_command(command: &CommandCollector) -> Option<String> {
+    let mut cmd = Command::new(&command.program);
+    cmd.args(&command.args);
+    #[cfg(target_os = "windows")]
+    cmd.creation_flags(CREATE_NO_WINDOW);
+    let output = output_with_timeout(cmd, APP_COLLECTOR_COMMAND_TIMEOUT).ok()?;
+    let text = String::from_utf8_lossy(&output.stdout).to_string();
+    let value = match command.pattern.as_deref() {
+        Some(pattern) => {
+            let caps = regex::Regex::new(pattern).ok()?.captures(&text)?;
+            caps.get(1).or_else(|| caps.get(0))?.as_str().to_string()
+        }
+        None => text.lines().next().unwrap_or_default().to_string(),
+    };
+    Some(value.trim().to_string()).filter(|v| !v.is_empty())
+}
+
+#[cfg(target_os = "windows")]
+const APP_REGISTRY_SCRIPT: &str = r#"
+$sources = '__SOURCES__' | ConvertFrom-Json
+$out = [ordered]@{}
+foreach ($src in @($sources)) {
+  foreach ($p in @($src.keys)) {
+    if (-not (Test-Path $p)) { continue }
+    $key = Get-Item -Path $p -ErrorAction SilentlyContinue
+    if ($src.subkey_match) {
+      $key = Get-ChildItem -Path $p -Recurse -ErrorAction SilentlyContinue |
+        Where-Object { $_.PSChildName -match $src.subkey_match } |
+        Select-Object -First 1
+    }
+    if (-not $key) { continue }
+    $item = Get-ItemProperty $key.PSPath -ErrorAction SilentlyContinue
+    if (-not $item) { continue }
+    foreach ($v in @($src.values)) {
+      foreach ($name in @($v.names)) {
+        $val = if ($name -eq '(key)') { $key.PSChildName } else { $item.$name }
+        if ($val) { $out[$v.label] = [string]$val; break }
+      }
+    }
+    break
+  }
+}
+$out | ConvertTo-Json -Compress
+"#;
+
+#[cfg(target_os = "windows")]
+fn collect_registry_values(
+    sources: &[RegistryCollector],
+    out: &mut serde_json::Map<String, serde_json::Value>,
+) {
+    if sources.is_empty() {
+        return;
+    }
+    let Ok(json) = serde_json::to_string(sources) else {
+        return;
+    };
+    // Embedded as a single-quoted PowerShell string. PowerShell also treats the
+    // typographic quotes as string delimiters, so everything non-ASCII is sent
+    // as a JSON \u escape.
+    let json: String = json
+        .chars()
+        .map(|c| {
+            if c.is_ascii() {
+                c.to_string()
+            } else {
+                c.encode_utf16(&mut [0; 2])
+                    .iter()
+                    .map(|unit| format!("\\u{unit:04x}"))
+                    .collect()
+            }
+        })
+        .collect();
+    let script = APP_REGISTRY_SCRIPT.replace("__SOURCES__", &json.replace('\'', "''"));
+    if let Some(serde_json::Value::Object(values)) = powershell_output(&script)
+        .ok()
+        .and_then(|text| serde_json::from_str(&text).ok())
+    {
+        out.extend(values);
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+fn collect_registry_values(
+    _sources: &[RegistryCollector],
+    _out: &mut serde_json::Map<String, serde_json::Value>,
+) {
+}
+
+fn run_app_collector(
+    collector: &AppCollector,
+    process_names: &[String],
+) -> serde_json::Map<String, serde_json::Value> {
+    let mut out = serde_json::Map::new();
+    collect_registry_values(&collector.registry, &mut out);
+    for command in &collector.commands {
+        if let Some(value) = run_collector_command(command) {
+            out.insert(command.label.clone(), value.into());
+        }
+    }
+    for file in &collector.files {
+        let paths: Vec<std::path::PathBuf> = file
+            .paths
+            .iter()
+            .flat_map(|p| expand_collector_path(p))
+            .collect();
+        collect_file_values(file, &paths, &mut out);
+    }
+    let running: Vec<&String> = collector
+        .processes
+        .iter()
+        .filter(|wanted| {
+            process_names
+                .iter()
+                .any(|actual| process_name_matches(actual, wanted))
+        })
+        .collect();
+    if !running.is_empty() {
+        out.insert(
+            "Running".to_string(),
+            running
+                .iter()
+                .map(|s| s.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+                .into(),
+        );
+    }
+    out
+}
+
+fn app_collectors_cache_path(app: &AppHandle) -> Option<std::path::PathBuf> {
+    app.path()
+        .app_local_data_dir()
+        .ok()
+        .map(|dir| dir.join(APP_COLLECTORS_FILE))
+}
+
+/// Like the antivirus catalog, a downloaded catalog only keeps commands that
+/// the bundled catalog already runs with the same arguments, and file and
+/// registry reads that the bundled catalog declares verbatim, so a catalog
+/// server cannot make the app run or read anything new. Health probes other
+/// than process checks touch paths and hosts, so they are restricted the same
+/// way.
+fn restrict_app_collectors(
+    mut catalog: AppCollectorCatalog,
+    bundled: &AppCollectorCatalog,
+) -> AppCollectorCatalog {
+    for collector in &mut catalog.collectors {
+        collector.commands.retain(|cmd| {
+            bundled.collectors.iter().any(|c| {
+                c.commands
+                    .iter()
+                    .any(|b| b.program == cmd.program && b.args == cmd.args)
+            })
+        });
+        collector
+            .files
+            .retain(|file| bundled.collectors.iter().any(|c| c.files.contains(file)));
+        collector
+            .registry
+            .retain(|reg| bundled.collectors.iter().any(|c| c.registry.contains(reg)));
+        collector.health.retain(|probe| {
+            probe.kind == "process" || bundled.collectors.iter().any(|c| c.health.contains(probe))
+        });
+    }
+    catalog
+}
+
+fn load_app_collectors(app: &AppHandle) -> AppCollectorCatalog {
+    let bundled = parse_app_collectors(APP_COLLECTORS_DEFAULT).unwrap_or_default();
+    if let Some(cached) = app_collectors_cache_path(app)
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|text| parse_app_collectors(&text).ok())
+    {
+        return restrict_app_collectors(cached, &bundled);
+    }
+    bundled
+}
+
+fn refresh_app_collectors_impl(app: &AppHandle) -> Result<AppCollectorCatalog, String> {
+    let url = std::env::var(APP_COLLECTORS_URL_ENV)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .ok_or_else(|| format!("{APP_COLLECTORS_URL_ENV} is not set"))?;
+    let text = download_catalog(&url, "app collectors")?;
+
+    // Validate before replacing the cached copy.
+    let catalog = restrict_app_collectors(
+        parse_app_collectors(&text)?,
+        &parse_app_collectors(APP_COLLECTORS_DEFAULT).unwrap_or_default(),
+    );
+    let path = app_collectors_cache_path(app).ok_or_else(|| "No app data dir".to_string())?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    std::fs::write(&path, text).map_err(|e| e.to_string())?;
+    Ok(catalog)
+}
+
+fn start_app_collectors_refresh(app: &AppHandle) {
+    if std::env::var(APP_COLLECTORS_URL_ENV).is_err() {
+        return;
+    }
+    let app_handle = app.clone();
+    std::thread::spawn(move || {
+        if let Err(err) = refresh_app_collectors_impl(&app_handle) {
+            eprintln!("App collector refresh failed: {err}");
+        }
+    });
+}
+
+#[tauri::command]
+async fn refresh_app_collectors(app: AppHandle) -> Result<usize, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        refresh_app_collectors_impl(&app).map(|catalog| catalog.collectors.len())
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+/// Runs every collector for `category` on this platform. A single collector's
+/// values are reported as-is; several (e.g. Thunderbird and Evolution for
+/// "email") are prefixed with the collector name.
+fn collect_app_context(app: &AppHandle, category: &str) -> Option<String> {
+    let catalog = load_app_collectors(app);
+    let collectors: Vec<&AppCollector> = catalog.matching(category, current_platform()).collect();
+    if collectors.is_empty() {
+        return None;
+    }
+    let mut system = System::new();
+    if collectors.iter().any(|c| !c.processes.is_empty()) {
+        system.refresh_processes();
+    }
+    let process_names: Vec<String> = system
+        .processes()
+        .values()
+        .map(|p| p.name().to_string())
+        .collect();
+
+    let results: Vec<(String, serde_json::Map<String, serde_json::Value>)> = collectors
+        .iter()
+        .map(|c| (c.name.clone(), run_app_collector(c, &process_names)))
+        .filter(|(_, values)| !values.is_empty())
+        .collect();
+    let details = match results.len() {
+        0 => return None,
+        1 => results.into_iter().next().map(|(_, values)| values)?,
+        _ => results
+            .into_iter()
+            .flat_map(|(name, values)| {
+                values
+                    .into_iter()
+                    .map(move |(label, value)| (format!("{name} {label}"), value))
+            })
+            .collect(),
+    };
+    serde_json::to_string(&details).ok()
+}
+
+#[tauri::command]
+async fn get_app_context(app: AppHandle, category: String) -> Result<AppContextInfo, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let details = collect_app_context(&app, &category);
+        Ok(AppContextInfo { category, details })
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+//
+// ───────── App health probes ─────────
+//
+
+const HEALTH_PROBE_DEFAULT_TIMEOUT_SECS: u64 = 5;
+
+/// One check in an application's health checklist. `kind` selects which
+/// fields apply: "process" (`processes`, any running passes), "path" (`path`
+/// or the collector value named by `path_from`, optionally `writable`),
+/// "tcp" (`host`, `port`) or "url" (`url`, optional `expect_status`).
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+struct HealthProbe {
+    kind: String,
+    label: Option<String>,
+    processes: Vec<String>,
+    path: Option<String>,
+    path_from: Option<String>,
+    writable: bool,
+    host: Option<String>,
+    port: Option<u16>,
+    url: Option<String>,
+    expect_status: Option<u16>,
+    timeout_secs: Option<u64>,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct HealthCheckResult {
+    label: String,
+    kind: String,
+    passed: bool,
+    detail: String,
+    latency_ms: Option<u64>,
+}
+
+#[derive(Serialize, Default)]
+struct AppHealthReport {
+    application: String,
+    checks: Vec<HealthCheckResult>,
+    passed: usize,
+    failed: usize,
+    timestamp: String,
+}
+
+impl HealthProbe {
+    fn timeout(&self) -> Duration {
+        Duration::from_secs(
+            self.timeout_secs
+                .unwrap_or(HEALTH_PROBE_DEFAULT_TIMEOUT_SECS)
+                .max(1),
+        )
+    }
+
+    fn default_label(&self) -> String {
+        match self.kind.as_str() {
+            "process" => format!("{} running", self.processes.join(" / ")),
+            "path" => format!(
+                "{} reachable{}",
+                self.path
+                    .as_deref()
+                    .or(self.path_from.as_deref())
+                    .unwrap_or("Path"),
+                if self.writable { " and writable" } else { "" }
+            ),
+            "tcp" => format!(
+                "{}:{} answers",
+                self.host.as_deref().unwrap_or("?"),
+                self.port.unwrap_or(0)
+            ),
+            "url" => format!("{} responds", self.url.as_deref().unwrap_or("?")),
+            other => other.to_string(),
+        }
+    }
+}
+
+/// Checks a (possibly network) path on a worker thread so a hung share
+/// can only cost the timeout; the thread is abandoned if it never returns.
+fn probe_path(path: &str, writable: bool, timeout: Duration) -> Result<String, String> {
+    let path = std::path::PathBuf::from(path);
+    let (tx, rx) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let result = std::fs::metadata(&path)
+            .map_err(|e| format!("Not reachable: {e}"))
+            .and_then(|meta| {
+                if !writable {
+                    return Ok("Reachable".to_string());
+                }
+                if !meta.is_dir() {
+                    return Err("Not a directory".to_string());
+                }
+                let probe = path.join(format!(".golpac-probe-{}", std::process::id()));
+                std::fs::write(&probe, b"golpac")
+                    .map_err(|e| format!("Reachable but not writable: {e}"))?;
+                let _ = std::fs::remove_file(&probe);
+                Ok("Reachable and writable".to_string())
+            });
+        let _ = tx.send(result);
+    });
+    rx.recv_timeout(timeout)
+        .unwrap_or_else(|_| Err(format!("No response after {}s", timeout.as_secs())))
+}
+
+fn probe_url(url: &str, expect_status: Option<u16>, timeout: Duration) -> Result<String, String> {
+    let response = Client::builder()
+        .timeout(timeout)
+        .build()
+        .map_err(|e| format!("Failed to build client: {e}"))?
+        .get(url)
+        .send()
+        .map_err(|e| format!("Request failed: {e}"))?;
+    let status = response.status();
+    let ok = match expect_status {
+        Some(expected) => status.as_u16() == expected,
+        None => !status.is_client_error() && !status.is_server_error(),
+    };
+    if ok {
+        Ok(format!("HTTP {}", status.as_u16()))
+    } else {
+        Err(format!("HTTP {}", status.as_u16()))
+    }
+}
+
+fn run_health_probe(
+    probe: &HealthProbe,
+    values: &serde_json::Map<String, serde_json::Value>,
+    process_names: &[String],
+) -> HealthCheckResult {
+    let started = std::time::Instant::now();
+    let timeout = probe.timeout();
+    let outcome: Result<String, String> = match probe.kind.as_str() {
+        "process" => {
+            let running: Vec<&String> = process_names
+                .iter()
+                .filter(|actual| {
+                    probe
+                        .processes
+                        .iter()
+                        .any(|wanted| process_name_matches(actual, wanted))
+                })
+                .collect();
+            match running.first() {
+                Some(name) => Ok(format!("{name} is running")),
+                None => Err("Not running".to_string()),
+            }
+        }
+        "path" => {
+            let path = probe.path.clone().or_else(|| {
+                probe
+                    .path_from
+                    .as_ref()
+                    .and_then(|label| values.get(label))
+                    .and_then(json_scalar)
+            });
+            match path.filter(|p| !p.trim().is_empty()) {
+                Some(path) => probe_path(&path, probe.writable, timeout)
+                    .map(|detail| format!("{detail}: {path}"))
+                    .map_err(|detail| format!("{detail}: {path}")),
+                None => Err("Path is not configured on this machine".to_string()),
+            }
+        }
+        "tcp" => match (probe.host.as_deref(), probe.port) {
+            (Some(host), Some(port)) => tcp_probe(host, port, timeout)
+                .map(|ms| format!("Connected to {host}:{port} in {ms} ms")),
+            _ => Err("Probe needs a host and port".to_string()),
+        },
+        "url" => match probe.url.as_deref() {
+            Some(url) => probe_url(url, probe.expect_status, timeout),
+            None => Err("Probe needs a url".to_string()),
+        },
+        other => Err(format!("Unknown probe kind \"{other}\"")),
+    };
+    let latency_ms = matches!(probe.kind.as_str(), "path" | "tcp" | "url")
+        .then(|| started.elapsed().as_millis() as u64);
+    let passed = outcome.is_ok();
+    HealthCheckResult {
+        label: probe.label.clone().unwrap_or_else(|| probe.default_label()),
+        kind: probe.kind.clone(),
+        passed,
+        detail: outcome.unwrap_or_else(|e| e),
+        latency_ms,
+    }
+}
+
+fn app_health_report(collector: &AppCollector, process_names: &[String]) -> AppHealthReport {
+    // Collected values feed `path_from` probes (e.g. Sage SharedData).
+    let values = if collector.health.iter().any(|p| p.path_from.is_some()) {
+        run_app_collector(collector, process_names)
+    } else {
+        serde_json::Map::new()
+    };
+    let checks: Vec<HealthCheckResult> = collector
+        .health
+        .iter()
+        .map(|probe| run_health_probe(probe, &values, process_names))
+        .collect();
+    let passed = checks.iter().filter(|c| c.passed).count();
+    AppHealthReport {
+        application: collector.name.clone(),
+        failed: checks.len() - passed,
+        passed,
+        checks,
+        timestamp: Utc::now().to_rfc3339(),
+    }
+}
+
+/// Health checklist for the applications matching `category`, or for every
+/// application with probes when no category is given.
+#[tauri::command]
+async fn get_app_health(
+    app: AppHandle,
+    category: Option<String>,
+) -> Result<Vec<AppHealthReport>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let catalog = load_app_collectors(&app);
+        let platform = current_platform();
+        let collectors: Vec<&AppCollector> = match category.as_deref() {
+            Some(category) => catalog.matching(category, platform).collect(),
+            None => catalog.for_platform(platform).collect(),
+        };
+        let collectors: Vec<&AppCollector> = collectors
+            .into_iter()
+            .filter(|c| !c.health.is_empty())
+            .collect();
+
+        let mut system = System::new();
+        system.refresh_processes();
+        let process_names: Vec<String> = system
+            .processes()
+            .values()
+            .map(|p| p.name().to_string())
+            .collect();
+        Ok(collectors
+            .into_iter()
+            .map(|c| app_health_report(c, &process_names))
+            .collect())
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+//
+// ───────── Mail server test ─────────
+//
+
+// Comma-separated hosts tested when the UI doesn't name one.
+const MAIL_SERVERS_ENV: &str = "GOLPAC_MAIL_SERVERS";
+const MAIL_DEFAULT_TIMEOUT_SECS: u64 = 10;
+// IMAPS, submission (STARTTLS), SMTPS and SMTP relay (STARTTLS).
+const MAIL_DEFAULT_ENDPOINTS: [(u16, &str); 4] =
+    [(993, "imaps"), (587, "smtp"), (465, "smtps"), (25, "smtp")];
+const MAIL_CERT_EXPIRY_WARNING_DAYS: i64 = 14;
+
+/// `protocol` is "imaps" or "smtps" (TLS from the start) or "imap"/"smtp"
+/// (plain connection upgraded with STARTTLS).
+#[derive(Deserialize, Clone)]
+struct MailEndpoint {
+    port: u16,
+    protocol: String,
+}
+
+/// Credentials are only used for this test run; they are never written to
+/// disk or logs and are only sent over a TLS connection whose certificate
+/// verified.
+/// `accept_invalid_certs` only lets the test pass with an untrusted
+/// certificate; it never releases credentials.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct MailTestOptions {
+    endpoints: Vec<MailEndpoint>,
+    timeout_secs: Option<u64>,
+    accept_invalid_certs: bool,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct MailDnsResult {
+    resolved: bool,
+    addresses: Vec<String>,
+    latency_ms: Option<u64>,
+    error: Option<String>,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct MailCertificate {
+    // Chain and host name verified against the system and bundled roots.
+    valid: bool,
+    error: Option<String>,
+    not_before: Option<String>,
+    not_after: Option<String>,
+    days_left: Option<i64>,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct MailAuthResult {
+    succeeded: bool,
+    detail: String,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct MailEndpointResult {
+    port: u16,
+    protocol: String,
+    tcp_connected: bool,
+    // The resolved address that accepted the connection.
+    address: Option<String>,
+    connect_ms: Option<u64>,
+    banner: Option<String>,
+    // From starting the connection to the server's greeting.
+    banner_ms: Option<u64>,
+    starttls_offered: Option<bool>,
+    tls_established: bool,
+    tls_version: Option<String>,
+    cipher_suite: Option<String>,
+    certificate: Option<MailCertificate>,
+    auth: Option<MailAuthResult>,
+    passed: bool,
+    error: Option<String>,
+    warnings: Vec<String>,
+}
+
+#[derive(Serialize, Default)]
+struct MailServerReport {
+    host: String,
+    dns: MailDnsResult,
+    endpoints: Vec<MailEndpointResult>,
+    passed: bool,
+    timestamp: String,
+}
+
+/// notBefore/notAfter of a DER certificate.
+fn der_cert_validity(der: &[u8]) -> Option<(chrono::DateTime<Utc>, chrono::DateTime<Utc>)> {
+    let (_, cert, _) = der_next(der)?;
+    let (_, tbs, _) = der_next(cert)?;
+    let mut rest = tbs;
+    let (tag, _, after_version) = der_next(rest)?;
+    if tag == 0xa0 {
+        rest = after_version;
+    }
+    let (_, _, rest) = der_next(rest)?; // serialNumber
+    let (_, _, rest) = der_next(rest)?; // signature
+    let (_, _, rest) = der_next(rest)?; // issuer
+    let (_, validity, _) = der_next(rest)?;
+    let (tag, not_before, rest) = der_next(validity)?;
+    let not_before = der_time(tag, not_before)?;
+    let (tag, not_after, _) = der_next(rest)?;
+    Some((not_before, der_time(tag, not_after)?))
+}
+
+/// Reads one CRLF-terminated line a byte at a time, so nothing past a
+/// STARTTLS reply is consumed before the TLS handshake.
+fn mail_read_line(stream: &mut impl std::io::Read) -> Result<String, String> {
+    let mut line = Vec::new();
+    let mut byte = [0u8; 1];
+    while line.len() < 8192 {
+        match stream.read(&mut byte) {
+            Ok(0) => break,
+            Ok(_) => {
+                line.push(byte[0]);
+                if byte[0] == b'\n' {
+                    break;
+                }
+            }
+            Err(e) => return Err(format!("Read failed: {e}")),
+        }
+    }
+    if line.is_empty() {
+        return Err("Connection closed by server".to_string());
+    }
+    Ok(String::from_utf8_lossy(&line).trim_end().to_string())
+}
+
+/// A possibly multi-line SMTP reply (`250-...` continues, `250 ...` ends).
+fn smtp_reply(stream: &mut impl std::io::Read) -> Result<(u16, Vec<String>), String> {
+    let mut lines = Vec::new();
+    loop {
+        let line = mail_read_line(stream)?;
+        let code: u16 = line
+            .get(..3)
+            .and_then(|c| c.parse().ok())
+            .ok_or_else(|| format!("Unexpected SMTP reply: {line}"))?;
+        let done = line.as_bytes().get(3) != Some(&b'-');
+        lines.push(line.get(4..).unwrap_or_default().to_string());
+        if done {
+            return Ok((code, lines));
+        }
+    }
+}
+
+fn smtp_command(
+    stream: &mut (impl std::io::Read + std::io::Write),
+    command: &str,
+) -> Result<(u16, Vec<String>), String> {
+    stream
+        .write_all(format!("{command}\r\n").as_bytes())
+        .and_then(|_| stream.flush())
+        .map_err(|e| format!("Write failed: {e}"))?;
+    smtp_reply(stream)
+}
+
+/// Sends a tagged IMAP command and returns the tagged completion line.
+fn imap_command(
+    stream: &mut (impl std::io::Read + std::io::Write),
+    tag: &str,
+    command: &str,
+) -> Result<(String, Vec<String>), String> {
+    stream
+        .write_all(format!("{tag} {command}\r\n").as_bytes())
+        .and_then(|_| stream.flush())
+        .map_err(|e| format!("Write failed: {e}"))?;
+    let mut untagged = Vec::new();
+    loop {
+        let line = mail_read_line(stream)?;
+        if let Some(rest) = line.strip_prefix(&format!("{tag} ")) {
+            return Ok((rest.to_string(), untagged));
+        }
+        untagged.push(line);
+    }
+}
+
+fn imap_quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+type MailCertOutcome = std::sync::Arc<std::sync::Mutex<Option<(Result<(), String>, Vec<u8>)>>>;
+
+/// Runs the normal WebPKI checks but records the verdict instead of
+/// aborting, so expired or mismatched certificates can be reported.
+struct RecordingCertVerifier {
+    inner: rustls::client::WebPkiVerifier,
+    outcome: MailCertOutcome,
+}
+
+impl rustls::client::ServerCertVerifier for RecordingCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &rustls::Certificate,
+        intermediates: &[rustls::Certificate],
+        server_name: &rustls::ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: std::time::SystemTime,
+    ) -> Result<rustls::client::ServerCertVerified, rustls::Error> {
+        let verdict = self
+            .inner
+            .verify_server_cert(
+                end_entity,
+                intermediates,
+                server_name,
+                scts,
+                ocsp_response,
+                now,
+            )
+            .map(|_| ())
+            .map_err(|e| e.to_string());
+        if let Ok(mut outcome) = self.outcome.lock() {
+            *outcome = Some((verdict, end_entity.0.clone()));
+        }
+        Ok(rustls::client::ServerCertVerified::assertion())
+    }
+}
+
+/// System trust store (so internal CAs count) plus the bundled Mozilla roots.
+fn mail_root_store() -> rustls::RootCertStore {
+    static ROOTS: once_cell::sync::Lazy<rustls::RootCertStore> = once_cell::sync::Lazy::new(|| {
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+            rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+                ta.subject,
+                ta.spki,
+                ta.name_constraints,
+            )
+        }));
+        for cert in rustls_native_certs::load_native_certs().unwrap_or_default() {
+            let _ = roots.add(&rustls::Certificate(cert.0));
+        }
+        roots
+    });
+    ROOTS.clone()
+}
+
+fn mail_start_tls(
+    tcp: TcpStream,
+    host: &str,
+    roots: &rustls::RootCertStore,
+    result: &mut MailEndpointResult,
+    accept_invalid_certs: bool,
+) -> Result<rustls::StreamOwned<rustls::ClientConnection, TcpStream>, String> {
+    let outcome: MailCertOutcome = Default::default();
+    let config = rustls::ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(std::sync::Arc::new(RecordingCertVerifier {
+            inner: rustls::client::WebPkiVerifier::new(roots.clone(), None),
+            outcome: outcome.clone(),
+        }))
+        .with_no_client_auth();
+    let server_name = rustls::ServerName::try_from(host)
+        .map_err(|e| format!("Invalid TLS server name {host}: {e}"))?;
+    let conn = rustls::ClientConnection::new(std::sync::Arc::new(config), server_name)
+        .map_err(|e| format!("TLS setup failed: {e}"))?;
+    let mut tls = rustls::StreamOwned::new(conn, tcp);
+    while tls.conn.is_handshaking() {
+        tls.conn
+            .complete_io(&mut tls.sock)
+            .map_err(|e| format!("TLS handshake failed: {e}"))?;
+    }
+
+    result.tls_established = true;
+    result.tls_version = tls.conn.protocol_version().map(|v| format!("{v:?}"));
+    result.cipher_suite = tls
+        .conn
+        .negotiated_cipher_suite()
+        .map(|s| format!("{:?}", s.suite()));
+    let recorded = outcome.lock().ok().and_then(|mut o| o.take());
+    if let Some((verdict, der)) = recorded {
+        let validity = der_cert_validity(&der);
+        let days_left = validity.map(|(_, not_after)| (not_after - Utc::now()).num_days());
+        if let Some(days) = days_left.filter(|d| (0..=MAIL_CERT_EXPIRY_WARNING_DAYS).contains(d)) {
+            result
+                .warnings
+                .push(format!("Server certificate expires in {days} day(s)."));
+        }
+        if verdict.is_err() && accept_invalid_certs {
+            result
+                .warnings
+                .push("Certificate is not trusted; accepted for this test only.".to_string());
+        }
+        result.certificate = Some(MailCertificate {
+            valid: verdict.is_ok(),
+            error: verdict.err(),
+            not_before: validity.map(|(nb, _)| nb.to_rfc3339()),
+            not_after: validity.map(|(_, na)| na.to_rfc3339()),
+            days_left,
+        });
+    }
+    Ok(tls)
+}
+
+fn mail_smtp_auth(
+    stream: &mut (impl std::io::Read + std::io::Write),
+    capabilities: &[String],
+    username: &str,
+    password: &str,
+) -> MailAuthResult {
+    let offers_plain = capabilities.iter().any(|c| {
+        let upper = c.to_uppercase();
+        upper.starts_with("AUTH") && upper.split_whitespace().any(|m| m == "PLAIN")
+    });
+    if !offers_plain {
+        return MailAuthResult {
+            succeeded: false,
+            detail: "Server does not offer AUTH PLAIN".to_string(),
+        };
+    }
+    let token = general_purpose::STANDARD.encode(format!("\0{username}\0{password}"));
+    match smtp_command(stream, &format!("AUTH PLAIN {token}")) {
+        Ok((235, _)) => MailAuthResult {
+            succeeded: true,
+            detail: "Authenticated".to_string(),
+        },
+        Ok((code, lines)) => MailAuthResult {
+            succeeded: false,
+            detail: format!("{code} {}", lines.join(" ")),
+        },
+        Err(e) => MailAuthResult {
+            succeeded: false,
+            detail: e,
+        },
+    }
+}
+
+fn mail_imap_auth(
+    stream: &mut (impl std::io::Read + std::io::Write),
+    username: &str,
+    password: &str,
+) -> MailAuthResult {
+    let command = format!("LOGIN {} {}", imap_quote(username), imap_quote(password));
+    match imap_command(stream, "g2", &command) {
+        Ok((status, _)) => MailAuthResult {
+            succeeded: status.starts_with("OK"),
+            detail: status,
+        },
+        Err(e) => MailAuthResult {
+            succeeded: false,
+            detail: e,
+        },
+    }
+}
+
+/// Protocol dialog after the TCP connect: greeting, STARTTLS where needed,
+/// TLS and the optional login.
+fn mail_dialog(
+    mut tcp: TcpStream,
+    host: &str,
+    roots: &rustls::RootCertStore,
+    endpoint: &MailEndpoint,
+    options: &MailTestOptions,
+    started: std::time::Instant,
+    result: &mut MailEndpointResult,
+) -> Result<(), String> {
+    let implicit_tls = matches!(endpoint.protocol.as_str(), "imaps" | "smtps");
+    let is_imap = endpoint.protocol.starts_with("imap");
+
+    let mut tls = if implicit_tls {
+        mail_start_tls(tcp, host, roots, result, options.accept_invalid_certs)?
+    } else {
+        let banner = if is_imap {
+            mail_read_line(&mut tcp)?
+        } else {
+            let (code, lines) = smtp_reply(&mut tcp)?;
+            if code != 220 {
+                return Err(format!(
+                    "Server refused connection: {code} {}",
+                    lines.join(" ")
+                ));
+            }
+            format!("{code} {}", lines.first().cloned().unwrap_or_default())
+        };
+        result.banner_ms = Some(started.elapsed().as_millis() as u64);
+        result.banner = Some(banner);
+
+        let offered = if is_imap {
+            let (_, untagged) = imap_command(&mut tcp, "g0", "CAPABILITY")?;
+            untagged
+                .iter()
+                .any(|l| l.to_uppercase().contains("STARTTLS"))
+        } else {
+            let (_, caps) = smtp_command(&mut tcp, "EHLO golpac-support")?;
+            caps.iter().any(|c| c.eq_ignore_ascii_case("STARTTLS"))
+        };
+        result.starttls_offered = Some(offered);
+        if !offered {
+            return Err("Server does not offer STARTTLS".to_string());
+        }
+        let accepted = if is_imap {
+            imap_command(&mut tcp, "g1", "STARTTLS")?
+                .0
+                .starts_with("OK")
+        } else {
+            smtp_command(&mut tcp, "STARTTLS")?.0 == 220
+        };
+        if !accepted {
+            return Err("Server rejected STARTTLS".to_string());
+        }
+        mail_start_tls(tcp, host, roots, result, options.accept_invalid_certs)?
+    };
+
+    if implicit_tls {
+        let banner = if is_imap {
+            mail_read_line(&mut tls)?
+        } else {
+            let (code, lines) = smtp_reply(&mut tls)?;
+            format!("{code} {}", lines.first().cloned().unwrap_or_default())
+        };
+        result.banner_ms = Some(started.elapsed().as_millis() as u64);
+        result.banner = Some(banner);
+    }
+
+    let capabilities = if is_imap {
+        Vec::new()
+    } else {
+        smtp_command(&mut tls, "EHLO golpac-support")?.1
+    };
+    let trusted = result
+        .certificate
+        .as_ref()
+        .map(|c| c.valid)
+        .unwrap_or(false);
+    if let (Some(username), Some(password)) = (&options.username, &options.password) {
+        result.auth = Some(if !trusted {
+            MailAuthResult {
+                succeeded: false,
+                detail: "Skipped: certificate is not trusted".to_string(),
+            }
+        } else if is_imap {
+            mail_imap_auth(&mut tls, username, password)
+        } else {
+            mail_smtp_auth(&mut tls, &capabilities, username, password)
+        });
+    }
+
+    // Best-effort polite close.
+    let _ = if is_imap {
+        imap_command(&mut tls, "g9", "LOGOUT").map(|_| ())
+    } else {
+        smtp_command(&mut tls, "QUIT").map(|_| ())
+    };
+    Ok(())
+}
+
+/// Connects to each resolved address in turn, like `tcp_probe`, and runs the
+/// dialog on the first one that accepts.
+fn test_mail_endpoint(
+    host: &str,
+    addrs: &[SocketAddr],
+    roots: &rustls::RootCertStore,
+    endpoint: &MailEndpoint,
+    options: &MailTestOptions,
+    timeout: Duration,
+) -> MailEndpointResult {
+    let mut result = MailEndpointResult {
+        port: endpoint.port,
+        protocol: endpoint.protocol.clone(),
+        ..Default::default()
+    };
+    let mut last_error = format!("No addresses for {host}");
+    let mut connected = None;
+    for addr in addrs.iter().map(|a| SocketAddr::new(a.ip(), endpoint.port)) {
+        let started = std::time::Instant::now();
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(tcp) => {
+                connected = Some((tcp, addr, started));
+                break;
+            }
+            Err(e) => last_error = format!("{addr}: {e}"),
+        }
+    }
+    let Some((tcp, addr, started)) = connected else {
+        result.error = Some(format!("TCP connect failed: {last_error}"));
+        return result;
+    };
+    result.tcp_connected = true;
+    result.address = Some(addr.ip().to_string());
+    result.connect_ms = Some(started.elapsed().as_millis() as u64);
+    let _ = tcp.set_read_timeout(Some(timeout));
+    let _ = tcp.set_write_timeout(Some(timeout));
+
+    if let Err(e) = mail_dialog(tcp, host, roots, endpoint, options, started, &mut result) {
+        result.error = Some(e);
+    }
+    let cert_ok = result
+        .certificate
+        .as_ref()
+        .map(|c| c.valid || options.accept_invalid_certs)
+        .unwrap_or(false);
+    result.passed = result.error.is_none()
+        && result.tls_established
+        && cert_ok
+        && result.auth.as_ref().map(|a| a.succeeded).unwrap_or(true);
+    result
+}
+
+fn test_mail_server_impl(host: &str, options: &MailTestOptions) -> MailServerReport {
+    use std::net::ToSocketAddrs;
+
+    let timeout = Duration::from_secs(
+        options
+            .timeout_secs
+            .unwrap_or(MAIL_DEFAULT_TIMEOUT_SECS)
+            .max(1),
+    );
+    let endpoints: Vec<MailEndpoint> = if options.endpoints.is_empty() {
+        MAIL_DEFAULT_ENDPOINTS
+            .iter()
+            .map(|(port, protocol)| MailEndpoint {
+                port: *port,
+                protocol: protocol.to_string(),
+            })
+            .collect()
+    } else {
+        options.endpoints.clone()
+    };
+
+    let mut report = MailServerReport {
+        host: host.to_string(),
+        timestamp: Utc::now().to_rfc3339(),
+        ..Default::default()
+    };
+    let started = std::time::Instant::now();
+    let addrs: Vec<SocketAddr> = match (host, 0).to_socket_addrs() {
+        Ok(addrs) => addrs.collect(),
+        Err(e) => {
+            report.dns.error = Some(format!("DNS lookup failed: {e}"));
+            return report;
+        }
+    };
+    report.dns = MailDnsResult {
+        resolved: !addrs.is_empty(),
+        addresses: addrs.iter().map(|a| a.ip().to_string()).collect(),
+        latency_ms: Some(started.elapsed().as_millis() as u64),
+        error: None,
+    };
+    if addrs.is_empty() {
+        return report;
+    }
+
+    let roots = mail_root_store();
+    report.endpoints = endpoints
+        .iter()
+        .map(|endpoint| test_mail_endpoint(host, &addrs, &roots, endpoint, options, timeout))
+        .collect();
+    // Port 25 is often blocked on client networks, so by default it doesn't fail the test.
+    report.passed = report
+        .endpoints
+        .iter()
+        .filter(|e| !(options.endpoints.is_empty() && e.port == 25))
+        .all(|e| e.passed);
+    report
+}
+
+/// Tests `host`, or every server in GOLPAC_MAIL_SERVERS when none is given.
+#[tauri::command]
+async fn test_mail_servers(
+    host: Option<String>,
+    options: Option<MailTestOptions>,
+) -> Result<Vec<MailServerReport>, String> {
+    let hosts: Vec<String> = match host.filter(|h| !h.trim().is_empty()) {
+        Some(host) => vec![host.trim().to_string()],
+        None => std::env::var(MAIL_SERVERS_ENV)
+            .unwrap_or_default()
+            .split(',')
+            .map(|h| h.trim().to_string())
+            .filter(|h| !h.is_empty())
+            .collect(),
+    };
+    if hosts.is_empty() {
+        return Err(format!(
+            "No mail server given and {MAIL_SERVERS_ENV} is not set"
+        ));
+    }
+    let options = options.unwrap_or_default();
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok(hosts
+            .iter()
+            .map(|host| test_mail_server_impl(host, &options))
+            .collect())
+    })
+    .await
+    .map_err(|e| format!("Thread join error: {e}"))?
+}
+
+//
+// ───────── Tauri main ─────────
+//
+
+fn main() {
+    let builder = tauri::Builder::default()
+        .plugin(tauri_plugin_single_instance::init(|_app, _args, _cwd| {
+            #[cfg(target_os = "windows")]
+            {
+                reveal_main_window(&_app);
+            }
+        }))
+        .plugin(tauri_plugin_autostart::init(
+            MacosLauncher::LaunchAgent,
+            None,
+        ));
+
+    #[cfg(target_os = "windows")]
+    let builder = builder.plugin(tauri_plugin_notification::init());
+
+    builder
+        .invoke_handler(tauri::generate_handler![
+            get_system_info,
+            get_printers,
+            capture_screenshot,
+            launch_quick_assist,
+            get_system_metrics,
+            get_metrics_history,
+            get_app_context,
+            get_vpn_status,
+            test_internet_connection,
+            get_antivirus_status,
+            launch_antivirus,
+            get_driver_status,
+            exit_application,
+            read_ticket_history,
+            write_ticket_history,
+            get_usage_snapshot,
+            get_battery_status,
+            get_thermal_status,
+            get_memory_pressure,
+            get_encryption_status,
+            refresh_av_catalog,
+            get_update_status,
+            get_startup_items,
+            disable_startup_item,
+            undo_startup_change,
+            get_service_health,
+            restart_service,
+            collect_system_logs,
+            get_recent_crashes,
+            get_display_inventory,
+            get_usb_devices,
+            get_device_history,
+            get_audio_diagnostics,
+            test_microphone_level,
+            get_camera_diagnostics,
+            capture_camera_test_frame,
+            get_certificate_report,
+            get_directory_status,
+            get_network_mounts,
+            remount_network_mount,
+            get_browser_diagnostics,
+            refresh_app_collectors,
+            get_app_health,
+            test_mail_servers
+        ])
+        .setup(|app| {
+            if let Err(e) = app.autolaunch().enable() {
+                eprintln!("Failed to enable autostart: {e}");
+            }
+            #[cfg(not(target_os = "windows"))]
+            let _ = app;
+
+            #[cfg(target_os = "windows")]
+            {
+                setup_windows_tray(app)?;
+                ensure_notification_permission(&app.handle());
+                start_target_still_monitor(&app.handle());
+                start_video_recorder(&app.handle());
+                start_video_uploader(&app.handle());
+            }
+            monitor_network(app.handle().clone());
+            start_av_catalog_refresh(app.handle());
+            start_app_collectors_refresh(app.handle());
+            start_thermal_monitor();
+            start_crash_watcher(app.handle());
+            start_usb_monitor(app.handle());
+
+            Ok(())
+        })
+        // Close button → hide window (app keeps running)
+        .on_window_event(|window, event| {
+            if let WindowEvent::CloseRequested { api, .. } = event {
+                api.prevent_close();
+                let _ = window.hide();
+                #[cfg(target_os = "windows")]
+                {
+                    show_background_notification(&window.app_handle());
+                }
+            }
+        })
+        .run(tauri::generate_context!())
+        .expect("error while running tauri application");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //
+    // ───────── Metrics history ─────────
+    //
+
+    #[test]
+    fn metrics_history_keeps_battery_health_and_stays_bounded() {
+        let metrics = SystemMetrics {
+            timestamp: "2024-05-01T10:00:00+00:00".to_string(),
+            battery: Some(BatteryStatus {
+                ac_online: Some(false),
+                batteries: vec![BatteryInfo {
+                    name: "BAT0".to_string(),
+                    charge_percent: Some(42.0),
+                    wear_percent: Some(45.0),
+                    health: "Replace".to_string(),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        for _ in 0..METRICS_HISTORY_MAX + 5 {
+            record_metrics_sample(&metrics);
+        }
+        let history = get_metrics_history().unwrap();
+        assert_eq!(history.len(), METRICS_HISTORY_MAX);
+        let last = history.last().unwrap();
+        assert_eq!(last.ac_online, Some(false));
+        assert_eq!(last.batteries[0].wear_percent, Some(45.0));
+        assert_eq!(last.batteries[0].health, "Replace");
+    }
+
+    //
+    // ───────── Antivirus catalog ─────────
+    //
+
+    #[test]
+    fn bundled_av_catalog_parses() {
+        let catalog = parse_av_catalog(AV_CATALOG_DEFAULT).expect("bundled catalog");
+        assert!(catalog.products.iter().all(|p| !p.name.trim().is_empty()));
+        assert!(parse_av_catalog(r#"{"version": 1, "products": []}"#).is_err());
+        assert!(parse_av_catalog(r#"{"products": [{"name": " "}]}"#).is_err());
+        assert!(parse_av_catalog("not json").is_err());
+    }
+
+    #[test]
+    fn av_catalog_lookup_is_per_platform() {
+        let catalog = parse_av_catalog(AV_CATALOG_DEFAULT).unwrap();
+        assert!(catalog.for_platform("linux").all(|p| p.name != "Webroot"));
+        assert!(catalog.for_platform("windows").any(|p| p.name == "Webroot"));
+
+        assert_eq!(
+            catalog
+                .find("Webroot SecureAnywhere", "windows")
+                .map(|p| p.name.as_str()),
+            Some("Webroot")
+        );
+        assert!(catalog.find("Webroot SecureAnywhere", "linux").is_none());
+        assert_eq!(
+            catalog.find("  clamav ", "LINUX").map(|p| p.name.as_str()),
+            Some("ClamAV")
+        );
+        assert!(catalog.find("", "linux").is_none());
+        assert!(catalog.find("Norton", "windows").is_none());
+    }
+
+    #[test]
+    fn process_names_ignore_case_and_exe_suffix() {
+        assert!(process_name_matches("WRSA.exe", "wrsa"));
+        assert!(process_name_matches("mbam", "MBAM.EXE"));
+        assert!(process_name_matches(" clamd ", "clamd"));
+        assert!(!process_name_matches("clamd", "clamdscan"));
+        assert!(!process_name_matches("clamd", ""));
+        // Linux truncates comm to 15 bytes.
+        assert!(process_name_matches(
+            "sophos_threat_d",
+            "sophos_threat_detector"
+        ));
+        assert!(!process_name_matches(
+            "sophos_threat",
+            "sophos_threat_detector"
+        ));
+    }
+
+    #[test]
+    fn remote_av_catalog_keeps_only_bundled_commands() {
+        let bundled = parse_av_catalog(AV_CATALOG_DEFAULT).unwrap();
+        let remote = parse_av_catalog(
+            r#"{"products": [
+                {"name": "ClamAV", "launch": [{"program": "clamtk"}, {"program": "sh", "args": ["-c", "id"]}]},
+                {"name": "MDE", "status_command": {"program": "mdatp", "args": ["health"]}},
+                {"name": "Evil", "status_command": {"program": "mdatp", "args": ["uninstall"]}}
+            ]}"#,
+        )
+        .unwrap();
+        let restricted = restrict_av_commands(remote, &bundled);
+        let programs: Vec<&str> = restricted.products[0]
+            .launch
+            .iter()
+            .map(|l| l.program.as_str())
+            .collect();
+        assert_eq!(programs, ["clamtk"]);
+        assert!(restricted.products[1].status_command.is_some());
+        assert!(restricted.products[2].status_command.is_none());
+    }
+
+    #[test]
+    fn catalog_download_requires_https() {
+        let err =
+            download_catalog("http://example.invalid/av.json", "antivirus catalog").unwrap_err();
+        assert!(err.contains("non-https"), "{err}");
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn status_fields_parse_key_value_lines() {
+        let fields = parse_status_fields(
+            "healthy                                     : true\n\
+             definitions_updated_minutes_ago             : 42\n\
+             org_id                                      : \"abc:def\"\n\
+             no separator here\n",
+        );
+        assert_eq!(fields.get("healthy").map(String::as_str), Some("true"));
+        assert_eq!(
+            fields
+                .get("definitions_updated_minutes_ago")
+                .map(String::as_str),
+            Some("42")
+        );
+        assert_eq!(fields.get("org_id").map(String::as_str), Some("abc:def"));
+        assert_eq!(fields.len(), 3);
+    }
+
+    //
+    // ───────── Crash watcher ─────────
+    //
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn kernel_log_lines_carry_their_timestamp() {
+        let journal = "2024-05-01T10:00:07+0000 host kernel: x";
+        let dmesg = "2024-05-01T12:00:07,123456+02:00 x";
+        assert_eq!(
+            kernel_log_time(journal).map(|t| t.timestamp()),
+            kernel_log_time(dmesg).map(|t| t.timestamp())
+        );
+        assert!(kernel_log_time("[ 1234.5678] chrome[42]: segfault").is_none());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn kernel_crashes_match_watched_apps() {
+        let watched = vec!["chrome".to_string(), "thunderbird".to_string()];
+        let log = "2024-05-01T10:00:00+0000 host kernel: chrome[4242]: segfault at 0 ip 00007f sp 00007f error 4\n\
+                   2024-05-01T10:00:01+0000 host kernel: traps: thunderbird[77] general protection fault ip:7f\n\
+                   2024-05-01T10:00:02+0000 host kernel: gimp[99]: segfault at 0 ip 00007f\n\
+                   2024-05-01T10:00:03+0000 host kernel: traps: chrome[5000] trap invalid opcode ip:7f sp:7f error:0\n\
+                   2024-05-01T10:00:04+0000 host kernel: traps: chrome[5001] trap divide error ip:7f sp:7f error:0\n\
+                   2024-05-01T10:00:05+0000 host kernel: traps: chrome[5002] trap int3 ip:7f sp:7f error:0\n\
+                   2024-05-01T10:00:06+0000 host kernel: traps: chrome[5003] trap overflow ip:7f sp:7f error:0\n";
+        let crashes = parse_kernel_crashes(log, &watched);
+        assert_eq!(crashes.len(), 6);
+        assert_eq!(crashes[0].app, "chrome");
+        assert_eq!(crashes[0].pid, Some(4242));
+        assert_eq!(crashes[0].timestamp, "2024-05-01T10:00:00+00:00");
+        assert_eq!(crashes[1].app, "thunderbird");
+        assert_eq!(crashes[1].pid, Some(77));
+        let kinds: Vec<(&str, Option<&str>)> = crashes
+            .iter()
+            .map(|c| (c.kind.as_str(), c.signal.as_deref()))
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ("segfault", Some("SIGSEGV")),
+                ("segfault", Some("SIGSEGV")),
+                ("trap", Some("SIGILL")),
+                ("trap", Some("SIGFPE")),
+                ("trap", Some("SIGTRAP")),
+                ("trap", None),
+            ]
+        );
+    }
+
+    //
+    // ───────── Certificates ─────────
+    //
+
+    // Self-signed P-256 certificate for "localhost" (notBefore is a UTCTime,
+    // notAfter a GeneralizedTime), base64 DER.
+    const TEST_CERT_DER: &str = concat!(
+        "MIIBkzCCATigAwIBAgIUYDWuXZz6O1acA/1Ip2b/rkNidzQwCgYIKoZIzj0EAwIwFDESMBAGA1UE",
+        "AwwJbG9jYWxob3N0MCAXDTI2MTAxODEzMjYzMVoYDzIxMjYwOTI0MTMyNjMxWjAUMRIwEAYDVQQD",
+        "DAlsb2NhbGhvc3QwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAASD3y+j9AwwDHx8EHKpGhfSekPs",
+        "YgteP97OBXEAKyxXkj9lt4IOfPncQ8lCiDKzTDajUmnOp1dGShi9WySD4LQXo2YwZDAdBgNVHQ4E",
+        "FgQUxFLyvTyHikfyHgiv7wnc1YrLBR8wHwYDVR0jBBgwFoAUxFLyvTyHikfyHgiv7wnc1YrLBR8w",
+        "FAYDVR0RBA0wC4IJbG9jYWxob3N0MAwGA1UdEwEB/wQCMAAwCgYIKoZIzj0EAwIDSQAwRgIhAJ1g",
+        "ejpM77HZI+PXvOjg4pKJZIzx9DhPLBfQ++H0YUHmAiEAtgfVn8be8j7Yr0+XuKMHdIlKV6V9MUes",
+        "pgGqwhejRzg=",
+    );
+
+    #[cfg(target_os = "linux")]
+    fn der(tag: u8, contents: &[u8]) -> Vec<u8> {
+        let mut out = vec![tag, contents.len() as u8];
+        out.extend_from_slice(contents);
+        out
+    }
+
+    #[cfg(target_os = "linux")]
+    fn der_rdn(oid: &[u8], tag: u8, value: &str) -> Vec<u8> {
+        let attribute = [der(0x06, oid), der(tag, value.as_bytes())].concat();
+        der(0x31, &der(0x30, &attribute))
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn der_names_print_like_rfc2253() {
+        let name = [
+            der_rdn(&[0x55, 0x04, 0x06], 0x13, "US"),
+            der_rdn(&[0x55, 0x04, 0x0a], 0x0c, "Acme, Inc."),
+            der_rdn(&[0x55, 0x04, 0x03], 0x0c, "Acme Root CA"),
+        ]
+        .concat();
+        let (text, cn) = der_name(&name);
+        assert_eq!(text, "CN=Acme Root CA,O=Acme\\, Inc.,C=US");
+        assert_eq!(cn.as_deref(), Some("Acme Root CA"));
+        assert_eq!(
+            der_oid(&[0x2a, 0x86, 0x48, 0x86, 0xf7, 0x0d, 0x01, 0x09, 0x01]),
+            "1.2.840.113549.1.9.1"
+        );
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn pem_bundles_parse_without_openssl() {
+        let body = TEST_CERT_DER;
+        let bundle = format!(
+            "# local CA\n-----BEGIN CERTIFICATE-----\n{}\n{}\n-----END CERTIFICATE-----\n\
+             -----BEGIN CERTIFICATE-----\nnot base64!\n-----END CERTIFICATE-----\n",
+            &body[..64],
+            &body[64..]
+        );
+        let (certs, failed) = parse_pem_certificates(&bundle, "System trust store");
+        assert_eq!(failed, 1);
+        assert_eq!(certs.len(), 1);
+        let cert = &certs[0];
+        assert_eq!(cert.store, "System trust store");
+        assert_eq!(cert.subject, "CN=localhost");
+        assert_eq!(cert.issuer, "CN=localhost");
+        assert_eq!(cert.common_name.as_deref(), Some("localhost"));
+        assert_eq!(cert.not_after.as_deref(), Some("2126-09-24T13:26:31+00:00"));
+        assert!(cert.days_left.unwrap() > 36000);
+        assert_eq!(
+            cert.fingerprint_sha256,
+            "47:C5:B6:D3:00:62:41:CF:C8:9B:9E:4F:6F:07:A1:8F:0D:26:A9:D7:C9:B4:E9:0F:33:00:32:15:B5:4B:51:AD"
+        );
+        assert!(certificate_matches(cert, "localhost"));
+        assert!(certificate_matches(
+            cert,
+            "47c5b6d3006241cfc89b9e4f6f07a18f0d26a9d7c9b4e90f33003215b54b51ad"
+        ));
+    }
+
+    //
+    // ───────── User account ─────────
+    //
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn sudo_list_output_grants_root() {
+        let header =
+            "Matching Defaults entries for bob on host:\n    env_reset, secure_path=/usr/bin\n\n\
+                      User bob may run the following commands on host:\n";
+        assert!(sudo_allows_all(&format!("{header}    (ALL : ALL) ALL\n")));
+        assert!(sudo_allows_all(&format!(
+            "{header}    (root) NOPASSWD: ALL\n"
+        )));
+        assert!(!sudo_allows_all(&format!(
+            "{header}    (root) /usr/bin/apt\n"
+        )));
+        assert!(!sudo_allows_all(&format!("{header}    (backup) ALL\n")));
+        assert!(!sudo_allows_all(
+            "Sorry, user bob may not run sudo on host.\n"
+        ));
+    }
+
+    //
+    // ───────── App collectors ─────────
+    //
+
+    #[test]
+    fn remote_collectors_keep_only_bundled_commands() {
+        let bundled = parse_app_collectors(APP_COLLECTORS_DEFAULT).expect("bundled catalog");
+        let remote = parse_app_collectors(
+            r#"{"collectors": [{
+                "name": "LibreOffice",
+                "commands": [
+                    {"label": "Version", "program": "libreoffice", "args": ["--version"]},
+                    {"label": "Oops", "program": "libreoffice", "args": ["--convert-to", "pdf"]},
+                    {"label": "Shell", "program": "sh", "args": ["-c", "id"]}
+                ]
+            }]}"#,
+        )
+        .unwrap();
+        let restricted = restrict_app_collectors(remote, &bundled);
+        let labels: Vec<&str> = restricted.collectors[0]
+            .commands
+            .iter()
+            .map(|c| c.label.as_str())
+            .collect();
+        assert_eq!(labels, ["Version"]);
+    }
+
+    #[test]
+    fn remote_collectors_keep_only_bundled_file_and_registry_reads() {
+        let bundled = parse_app_collectors(APP_COLLECTORS_DEFAULT).expect("bundled catalog");
+        let remote = parse_app_collectors(
+            r#"{"collectors": [{
+                "name": "Thunderbird",
+                "files": [
+                    {"label": "Profile size", "paths": ["~/.thunderbird", "~/snap/thunderbird/common/.thunderbird"], "format": "size"},
+                    {"paths": ["~/.aws/credentials"], "format": "ini",
+                     "values": [{"label": "Key", "names": ["default.aws_secret_access_key"]}]},
+                    {"label": "Profile size", "paths": ["~/.ssh/id_rsa"], "format": "text"}
+                ],
+                "registry": [
+                    {"keys": ["HKLM:\\SOFTWARE\\ACCPAC International, Inc.\\ACCPAC\\Configuration",
+                              "HKLM:\\SOFTWARE\\WOW6432Node\\ACCPAC International, Inc.\\ACCPAC\\Configuration"],
+                     "values": [{"label": "Version", "names": ["Version"]},
+                                {"label": "SharedData", "names": ["SharedData"]}]},
+                    {"keys": ["HKLM:\\SOFTWARE\\Secrets"],
+                     "values": [{"label": "Token", "names": ["Token"]}]}
+                ]
+            }]}"#,
+        )
+        .unwrap();
+        let restricted = restrict_app_collectors(remote, &bundled);
+        let collector = &restricted.collectors[0];
+        assert_eq!(collector.files.len(), 1);
+        assert_eq!(collector.files[0].format, "size");
+        assert_eq!(collector.registry.len(), 1);
+        assert!(collector.registry[0].keys[0].contains("ACCPAC"));
+    }
+
+    //
+    // ───────── App health probes ─────────
+    //
+
+    #[test]
+    fn remote_health_probes_keep_only_bundled_targets() {
+        let bundled = parse_app_collectors(APP_COLLECTORS_DEFAULT).expect("bundled catalog");
+        let remote = parse_app_collectors(
+            r#"{"collectors": [{
+                "name": "Sage 300",
+                "health": [
+                    {"kind": "process", "label": "Anything running", "processes": ["notepad"]},
+                    {"kind": "path", "label": "SharedData reachable and writable",
+                     "path_from": "SharedData", "writable": true},
+                    {"kind": "path", "path": "C:\\Windows\\System32", "writable": true},
+                    {"kind": "tcp", "label": "coretechsolutions.app answers on 443",
+                     "host": "coretechsolutions.app", "port": 443},
+                    {"kind": "tcp", "host": "10.0.0.1", "port": 22},
+                    {"kind": "url", "url": "http://169.254.169.254/latest/meta-data/"}
+                ]
+            }]}"#,
+        )
+        .unwrap();
+        let restricted = restrict_app_collectors(remote, &bundled);
+        let labels: Vec<String> = restricted.collectors[0]
+            .health
+            .iter()
+            .map(|p| p.label.clone().unwrap_or_default())
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                "Anything running",
+                "SharedData reachable and writable",
+                "coretechsolutions.app answers on 443"
+            ]
+        );
+    }
+
+    //
+    // ───────── Mail server test ─────────
+    //
+
+    // PKCS#8 key for TEST_CERT_DER, base64 DER.
+    const TEST_KEY_DER: &str = concat!(
+        "MIGHAgEAMBMGByqGSM49AgEGCCqGSM49AwEHBG0wawIBAQQgR6h79dww6/39hbRnPXCMKl8f6Wt+",
+        "mzr0hT3rayGdasihRANCAASD3y+j9AwwDHx8EHKpGhfSekPsYgteP97OBXEAKyxXkj9lt4IOfPnc",
+        "Q8lCiDKzTDajUmnOp1dGShi9WySD4LQX",
+    );
+
+    /// Replays a scripted server and records what the client wrote.
+    struct ScriptedStream {
+        input: std::io::Cursor<Vec<u8>>,
+        written: Vec<u8>,
+    }
+
+    impl ScriptedStream {
+        fn new(input: &str) -> Self {
+            ScriptedStream {
+                input: std::io::Cursor::new(input.as_bytes().to_vec()),
+                written: Vec::new(),
+            }
+        }
+    }
+
+    impl std::io::Read for ScriptedStream {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            self.input.read(buf)
+        }
+    }
+
+    impl std::io::Write for ScriptedStream {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.written.write(buf)
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn mail_read_line_stops_at_the_line_end() {
+        let mut stream = ScriptedStream::new("* OK ready\r\nnext\n");
+        assert_eq!(mail_read_line(&mut stream).unwrap(), "* OK ready");
+        assert_eq!(mail_read_line(&mut stream).unwrap(), "next");
+        assert!(mail_read_line(&mut stream).is_err());
+
+        // Bytes after the STARTTLS reply belong to the TLS handshake.
+        let mut stream = ScriptedStream::new("220 go ahead\r\n\u{16}\u{3}\u{1}");
+        mail_read_line(&mut stream).unwrap();
+        assert_eq!(stream.input.position(), 14);
+    }
+
+    #[test]
+    fn smtp_reply_collects_continuation_lines() {
+        let mut stream =
+            ScriptedStream::new("250-mail.example.com\r\n250-STARTTLS\r\n250 AUTH PLAIN LOGIN\r\n");
+        let (code, lines) = smtp_reply(&mut stream).unwrap();
+        assert_eq!(code, 250);
+        assert_eq!(lines, ["mail.example.com", "STARTTLS", "AUTH PLAIN LOGIN"]);
+
+        assert!(smtp_reply(&mut ScriptedStream::new("hello\r\n")).is_err());
+        assert!(smtp_reply(&mut ScriptedStream::new("250-cut off\r\n")).is_err());
+    }
+
+    #[test]
+    fn imap_command_returns_tagged_status_and_untagged_lines() {
+        let mut stream = ScriptedStream::new("* CAPABILITY IMAP4rev1 STARTTLS\r\ng0 OK done\r\n");
+        let (status, untagged) = imap_command(&mut stream, "g0", "CAPABILITY").unwrap();
+        assert_eq!(status, "OK done");
+        assert_eq!(untagged, ["* CAPABILITY IMAP4rev1 STARTTLS"]);
+        assert_eq!(stream.written, b"g0 CAPABILITY\r\n");
+
+        assert!(imap_command(&mut ScriptedStream::new("* BYE\r\n"), "g1", "NOOP").is_err());
+        assert_eq!(imap_quote(r#"pa"ss\word"#), r#""pa\"ss\\word""#);
+    }
+
+    #[test]
+    fn der_cert_validity_reads_both_time_formats() {
+        let der = general_purpose::STANDARD.decode(TEST_CERT_DER).unwrap();
+        let (not_before, not_after) = der_cert_validity(&der).unwrap();
+        assert_eq!(not_before.to_rfc3339(), "2026-10-18T13:26:31+00:00");
+        assert_eq!(not_after.to_rfc3339(), "2126-09-24T13:26:31+00:00");
+
+        assert!(der_cert_validity(&der[..der.len() / 2]).is_none());
+        assert!(der_cert_validity(&[]).is_none());
+    }
+
+    /// Serves one SMTP session with STARTTLS and the test certificate,
+    /// returning the port and the commands it saw after the handshake.
+    fn spawn_smtp_server() -> (u16, std::thread::JoinHandle<Vec<String>>) {
+        use std::io::Write;
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let (mut tcp, _) = listener.accept().unwrap();
+            tcp.write_all(b"220 test ESMTP\r\n").unwrap();
+            assert!(mail_read_line(&mut tcp).unwrap().starts_with("EHLO "));
+            tcp.write_all(b"250-test\r\n250 STARTTLS\r\n").unwrap();
+            assert_eq!(mail_read_line(&mut tcp).unwrap(), "STARTTLS");
+            tcp.write_all(b"220 ready\r\n").unwrap();
+
+            let config = rustls::ServerConfig::builder()
+                .with_safe_defaults()
+                .with_no_client_auth()
+                .with_single_cert(
+                    vec![rustls::Certificate(
+                        general_purpose::STANDARD.decode(TEST_CERT_DER).unwrap(),
+                    )],
+                    rustls::PrivateKey(general_purpose::STANDARD.decode(TEST_KEY_DER).unwrap()),
+                )
+                .unwrap();
+            let conn = rustls::ServerConnection::new(std::sync::Arc::new(config)).unwrap();
+            let mut tls = rustls::StreamOwned::new(conn, tcp);
+            let mut commands = Vec::new();
+            while let Ok(line) = mail_read_line(&mut tls) {
+                let reply = match line.split_whitespace().next().unwrap_or_default() {
+                    "EHLO" => "250-test\r\n250 AUTH PLAIN\r\n",
+                    "AUTH" => "235 ok\r\n",
+                    "QUIT" => "221 bye\r\n",
+                    _ => "500 unknown\r\n",
+                };
+                tls.write_all(reply.as_bytes()).unwrap();
+                tls.flush().unwrap();
+                commands.push(line);
+                if reply.starts_with("221") {
+                    break;
+                }
+            }
+            commands
+        });
+        (port, server)
+    }
+
+    fn smtp_starttls_test(roots: &rustls::RootCertStore) -> (MailEndpointResult, Vec<String>) {
+        let (port, server) = spawn_smtp_server();
+        let options = MailTestOptions {
+            username: Some("user".to_string()),
+            password: Some("secret".to_string()),
+            ..Default::default()
+        };
+        let endpoint = MailEndpoint {
+            port,
+            protocol: "smtp".to_string(),
+        };
+        let addrs = ["127.0.0.1:0".parse().unwrap()];
+        let result = test_mail_endpoint(
+            "localhost",
+            &addrs,
+            roots,
+            &endpoint,
+            &options,
+            Duration::from_secs(5),
+        );
+        (result, server.join().unwrap())
+    }
+
+    #[test]
+    fn smtp_starttls_skips_credentials_for_untrusted_certificates() {
+        let (result, commands) = smtp_starttls_test(&rustls::RootCertStore::empty());
+
+        assert_eq!(result.error, None);
+        assert_eq!(result.address.as_deref(), Some("127.0.0.1"));
+        assert_eq!(result.banner.as_deref(), Some("220 test ESMTP"));
+        assert_eq!(result.starttls_offered, Some(true));
+        assert!(result.tls_established);
+        let cert = result.certificate.as_ref().unwrap();
+        assert!(!cert.valid);
+        assert_eq!(cert.not_after.as_deref(), Some("2126-09-24T13:26:31+00:00"));
+        let auth = result.auth.as_ref().unwrap();
+        assert!(!auth.succeeded);
+        assert_eq!(auth.detail, "Skipped: certificate is not trusted");
+        assert!(!result.passed);
+        assert!(!commands.iter().any(|c| c.starts_with("AUTH")));
+        assert_eq!(commands.last().map(String::as_str), Some("QUIT"));
+    }
+
+    #[test]
+    fn smtp_starttls_sends_credentials_to_trusted_certificates() {
+        let mut roots = rustls::RootCertStore::empty();
+        roots
+            .add(&rustls::Certificate(
+                general_purpose::STANDARD.decode(TEST_CERT_DER).unwrap(),
+            ))
+            .unwrap();
+        let (result, commands) = smtp_starttls_test(&roots);
+
+        assert_eq!(result.error, None);
+        assert!(result.certificate.as_ref().unwrap().valid);
+        assert!(result.auth.as_ref().unwrap().succeeded);
+        assert!(result.passed);
+        let token = general_purpose::STANDARD.encode("\0user\0secret");
+        assert!(commands.contains(&format!("AUTH PLAIN {token}")));
+        assert_eq!(commands.last().map(String::as_str), Some("QUIT"));
+    }
 }